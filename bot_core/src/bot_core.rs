@@ -1,11 +1,95 @@
-use teloxide::{Bot, prelude::Requester, types::ChatId};
+use sqlx::{Pool, Postgres};
+use teloxide::{
+    ApiError, Bot, RequestError,
+    prelude::Requester,
+    types::{ChatId, Message},
+};
+
+use crate::db::users::deactivate_user;
+
+/// Times a message is sent when Telegram keeps asking to retry it later
+pub const MAX_SEND_ATTEMPTS: u32 = 3;
 
 pub struct BotCore;
 
 impl BotCore {
     pub async fn send_message(bot: &Bot, chat_id: ChatId, text: String) {
-        if let Err(err) = bot.send_message(chat_id, &text).await {
+        if let Err(err) = BotCore::send_with_retry(bot, chat_id, &text).await {
             log::error!("Failed to send message: {}", err);
         }
     }
+
+    /// Sends the message, waiting for as long as Telegram asks when it limits the bot
+    async fn send_with_retry(
+        bot: &Bot,
+        chat_id: ChatId,
+        text: &str,
+    ) -> Result<Message, RequestError> {
+        let mut attempt = 1;
+
+        loop {
+            match bot.send_message(chat_id, text).await {
+                Err(RequestError::RetryAfter(seconds)) if attempt < MAX_SEND_ATTEMPTS => {
+                    log::warn!(
+                        "Sending to {} was limited, retrying after {} seconds",
+                        chat_id,
+                        seconds.seconds()
+                    );
+                    tokio::time::sleep(seconds.duration()).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends a scheduled notification to the user and tells whether it was sent. The users who
+    /// can't be reached anymore, e.g. because they blocked the bot, are marked inactive so the
+    /// jobs stop sending to them.
+    pub async fn send_notification(
+        bot: &Bot,
+        pool: &Pool<Postgres>,
+        chat_id: ChatId,
+        text: String,
+    ) -> bool {
+        let result = BotCore::send_with_retry(bot, chat_id, &text).await;
+        BotCore::notification_sent(pool, chat_id, result).await
+    }
+
+    /// Tells whether the notification was sent, marking the users who can't be reached anymore
+    /// inactive
+    pub async fn notification_sent(
+        pool: &Pool<Postgres>,
+        chat_id: ChatId,
+        result: Result<Message, RequestError>,
+    ) -> bool {
+        match result {
+            Ok(_) => true,
+            Err(err) if BotCore::is_chat_unreachable(&err) => {
+                log::warn!("Chat {} can't be reached anymore: {}", chat_id, err);
+                deactivate_user(pool, chat_id.0).await;
+                false
+            }
+            Err(err) => {
+                log::error!("Failed to send message: {}", err);
+                false
+            }
+        }
+    }
+
+    /// Whether the error means that no message can be sent to the chat until the user starts
+    /// the bot again
+    pub fn is_chat_unreachable(err: &RequestError) -> bool {
+        matches!(
+            err,
+            RequestError::Api(
+                ApiError::BotBlocked
+                    | ApiError::UserDeactivated
+                    | ApiError::ChatNotFound
+                    | ApiError::BotKicked
+                    | ApiError::BotKickedFromSupergroup
+                    | ApiError::BotKickedFromChannel
+            )
+        )
+    }
 }
@@ -0,0 +1,79 @@
+use chrono::NaiveDate;
+use sqlx::{Pool, Postgres};
+
+/// Outcome of [`claim_delivery`]
+pub enum DeliveryClaim {
+    /// The delivery is recorded, and has to be released if the notification isn't sent
+    Claimed,
+    /// The notification has already been delivered, or is being delivered by another job
+    AlreadyDelivered,
+    /// No user has the chat, so the delivery can't be recorded
+    UnknownUser,
+}
+
+/// Records the delivery of the notification `kind` for the `logical_date` to the user before it
+/// is sent, so no other job sends it again
+pub async fn claim_delivery(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    kind: &str,
+    logical_date: NaiveDate,
+) -> Result<DeliveryClaim, sqlx::Error> {
+    let inserted = sqlx::query!(
+        "
+            INSERT INTO deliveries (user_id, kind, logical_date)
+            SELECT id, $2, $3 FROM users WHERE chat_id = $1
+            ON CONFLICT (user_id, kind, logical_date) DO NOTHING
+        ",
+        chat_id,
+        kind,
+        logical_date
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if inserted > 0 {
+        return Ok(DeliveryClaim::Claimed);
+    }
+
+    let user_exists = sqlx::query_scalar!(
+        "SELECT EXISTS (SELECT 1 FROM users WHERE chat_id = $1)",
+        chat_id
+    )
+    .fetch_one(pool)
+    .await?
+    .unwrap_or_default();
+
+    if user_exists {
+        Ok(DeliveryClaim::AlreadyDelivered)
+    } else {
+        Ok(DeliveryClaim::UnknownUser)
+    }
+}
+
+/// Deletes the claimed delivery of a notification which wasn't sent, so it can be sent again
+pub async fn release_delivery(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    kind: &str,
+    logical_date: NaiveDate,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "
+            DELETE FROM deliveries
+            USING users
+            WHERE users.id = deliveries.user_id
+                AND users.chat_id = $1
+                AND deliveries.kind = $2
+                AND deliveries.logical_date = $3
+        ",
+        chat_id,
+        kind,
+        logical_date
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
+pub mod deliveries;
 pub mod migrator;
 pub mod postgres_metadata_store;
 pub mod postgres_notification_store;
 pub mod tables;
+pub mod users;
@@ -150,6 +150,7 @@ impl DataStore<JobStoredData> for PostgresMetadataStore {
                 }
             }
 
+            #[allow(clippy::collapsible_if)]
             if let Some(callbacks) = &callbacks {
                 if let Err(e) = callbacks.after_job_add(&job, &mut tx).await {
                     tx.rollback().await.map_err(|e| {
@@ -0,0 +1,16 @@
+use sqlx::{Pool, Postgres};
+
+/// Marks the user inactive, so the jobs stop sending notifications to them until they send
+/// `/start` again
+pub async fn deactivate_user(pool: &Pool<Postgres>, chat_id: i64) {
+    match sqlx::query!(
+        "UPDATE users SET active = FALSE WHERE chat_id = $1",
+        chat_id
+    )
+    .execute(pool)
+    .await
+    {
+        Ok(_) => log::info!("User {} deactivated", chat_id),
+        Err(e) => log::error!("Failed to deactivate user {}: {}", chat_id, e),
+    }
+}
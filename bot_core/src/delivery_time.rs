@@ -0,0 +1,17 @@
+use chrono::NaiveTime;
+
+/// Parses the time of the day the user wants to receive the notifications at, such as `20:30`,
+/// `20.30` or `20`. The seconds are dropped, as the jobs check the time by the minute.
+pub fn parse_delivery_time(value: &str) -> Option<NaiveTime> {
+    let value = value.trim().trim_end_matches('h');
+    let (hour, minute) = match value.split_once([':', '.']) {
+        Some((hour, minute)) => (hour, minute),
+        None => (value, "0"),
+    };
+
+    if minute.len() > 2 {
+        return None;
+    }
+
+    NaiveTime::from_hms_opt(hour.parse().ok()?, minute.parse().ok()?, 0)
+}
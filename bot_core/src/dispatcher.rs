@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::NaiveDate;
+use sqlx::{Pool, Postgres};
+use teloxide::{Bot, RequestError, prelude::Requester, types::ChatId};
+use tokio::sync::Semaphore;
+
+use crate::{
+    bot_core::{BotCore, MAX_SEND_ATTEMPTS},
+    db::deliveries::{DeliveryClaim, claim_delivery, release_delivery},
+};
+
+/// Messages per second Telegram allows a bot to send to all the chats together
+pub const GLOBAL_MESSAGES_PER_SECOND: u32 = 30;
+
+/// Interval Telegram allows between two messages to the same chat
+pub const CHAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Notifications of a broadcast delivered at the same time, each holding a database connection
+/// for the record of its delivery
+const MAX_CONCURRENT_DELIVERIES: usize = 5;
+
+/// A notification for one user in a broadcast
+pub struct Notification {
+    pub chat_id: ChatId,
+    pub messages: Vec<String>,
+    /// Kind and logical date under which the delivery is recorded in the `deliveries` table
+    pub delivery: Option<(String, NaiveDate)>,
+}
+
+impl Notification {
+    pub fn new(chat_id: ChatId, messages: Vec<String>) -> Self {
+        Self {
+            chat_id,
+            messages,
+            delivery: None,
+        }
+    }
+
+    /// Sends the notification only if the user hasn't received the notification `kind` for the
+    /// `logical_date` yet
+    pub fn once(mut self, kind: impl Into<String>, logical_date: NaiveDate) -> Self {
+        self.delivery = Some((kind.into(), logical_date));
+        self
+    }
+}
+
+/// Token bucket holding the messages to all the chats to the global rate, along with the
+/// interval between the messages to the same chat
+pub struct RateLimiter {
+    messages_per_second: f64,
+    tokens: f64,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+    chats: HashMap<ChatId, Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(messages_per_second: u32, now: Instant) -> Self {
+        let messages_per_second = messages_per_second.max(1) as f64;
+
+        Self {
+            messages_per_second,
+            tokens: messages_per_second,
+            refilled_at: now,
+            paused_until: None,
+            chats: HashMap::new(),
+        }
+    }
+
+    /// Takes the token for a message to the chat at the moment, or tells how long to wait
+    /// before trying again
+    pub fn try_acquire(&mut self, chat_id: ChatId, now: Instant) -> Result<(), Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if now < paused_until {
+                return Err(paused_until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.messages_per_second).min(self.messages_per_second);
+        self.refilled_at = now;
+
+        self.chats
+            .retain(|_, sent_at| now.saturating_duration_since(*sent_at) < CHAT_INTERVAL);
+        if let Some(sent_at) = self.chats.get(&chat_id) {
+            return Err(*sent_at + CHAT_INTERVAL - now);
+        }
+
+        if self.tokens < 1.0 {
+            // Rounded up, so the token has been refilled after the wait
+            let seconds = (1.0 - self.tokens) / self.messages_per_second;
+            return Err(Duration::from_micros((seconds * 1e6).ceil() as u64));
+        }
+
+        self.tokens -= 1.0;
+        self.chats.insert(chat_id, now);
+
+        Ok(())
+    }
+
+    /// Holds off all the messages until the moment, as asked by Telegram with `retry_after`
+    pub fn pause(&mut self, until: Instant) {
+        self.paused_until = Some(self.paused_until.map_or(until, |paused| paused.max(until)));
+    }
+}
+
+/// Sends the notifications of a bot within the limits of Telegram, which are shared by all the
+/// jobs of the bot
+#[derive(Clone)]
+pub struct NotificationDispatcher {
+    limiter: Arc<Mutex<RateLimiter>>,
+    spread: Option<Duration>,
+}
+
+impl Default for NotificationDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationDispatcher {
+    pub fn new() -> Self {
+        Self {
+            limiter: Arc::new(Mutex::new(RateLimiter::new(
+                GLOBAL_MESSAGES_PER_SECOND,
+                Instant::now(),
+            ))),
+            spread: None,
+        }
+    }
+
+    /// Reads `BROADCAST_SPREAD_SECONDS`, the window over which the notifications of a broadcast
+    /// are spread, and sends them as fast as allowed when it's missing
+    pub fn from_env() -> Self {
+        let dispatcher = Self::new();
+
+        match std::env::var("BROADCAST_SPREAD_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+        {
+            Some(seconds) => dispatcher.with_spread(Duration::from_secs(seconds)),
+            None => dispatcher,
+        }
+    }
+
+    pub fn with_messages_per_second(self, messages_per_second: u32) -> Self {
+        Self {
+            limiter: Arc::new(Mutex::new(RateLimiter::new(
+                messages_per_second,
+                Instant::now(),
+            ))),
+            ..self
+        }
+    }
+
+    /// Spreads the notifications of each broadcast evenly over the window, instead of sending
+    /// them as fast as allowed
+    pub fn with_spread(mut self, window: Duration) -> Self {
+        self.spread = Some(window);
+        self
+    }
+
+    /// Same dispatcher, sharing the limits, which sends the broadcasts as fast as allowed
+    pub fn without_spread(mut self) -> Self {
+        self.spread = None;
+        self
+    }
+
+    /// Waits until a message can be sent to the chat
+    pub async fn acquire(&self, chat_id: ChatId) {
+        loop {
+            let wait = match self.limiter.lock() {
+                Ok(mut limiter) => limiter.try_acquire(chat_id, Instant::now()),
+                Err(err) => {
+                    log::error!("Rate limiter poisoned: {}", err);
+                    return;
+                }
+            };
+
+            match wait {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    fn pause(&self, duration: Duration) {
+        if let Ok(mut limiter) = self.limiter.lock() {
+            limiter.pause(Instant::now() + duration);
+        }
+    }
+
+    /// Sends a message to the user and tells whether it was sent. When Telegram asks to retry
+    /// later, all the messages wait for as long as it asked.
+    pub async fn send(
+        &self,
+        bot: &Bot,
+        pool: &Pool<Postgres>,
+        chat_id: ChatId,
+        text: &str,
+    ) -> bool {
+        let mut attempt = 1;
+
+        loop {
+            self.acquire(chat_id).await;
+
+            match bot.send_message(chat_id, text).await {
+                Err(RequestError::RetryAfter(seconds)) if attempt < MAX_SEND_ATTEMPTS => {
+                    log::warn!(
+                        "Sending to {} was limited, retrying after {} seconds",
+                        chat_id,
+                        seconds.seconds()
+                    );
+                    self.pause(seconds.duration());
+                    attempt += 1;
+                }
+                result => return BotCore::notification_sent(pool, chat_id, result).await,
+            }
+        }
+    }
+
+    /// Sends the messages of the notification. The notifications sent once are claimed in the
+    /// `deliveries` table before they are sent, and released when the first message fails, so a
+    /// failed notification can be sent again.
+    pub async fn deliver(&self, bot: &Bot, pool: &Pool<Postgres>, notification: Notification) {
+        let Notification {
+            chat_id,
+            messages,
+            delivery,
+        } = notification;
+
+        if let Some((kind, logical_date)) = &delivery {
+            match claim_delivery(pool, chat_id.0, kind, *logical_date).await {
+                Ok(DeliveryClaim::Claimed) => {}
+                Ok(DeliveryClaim::AlreadyDelivered) => {
+                    log::info!(
+                        "Notification {} of {} for {} already delivered",
+                        kind,
+                        chat_id,
+                        logical_date
+                    );
+                    return;
+                }
+                Ok(DeliveryClaim::UnknownUser) => {
+                    log::warn!(
+                        "Notification {} for {} not sent, as no user has the chat",
+                        kind,
+                        chat_id
+                    );
+                    return;
+                }
+                Err(err) => {
+                    log::error!("Failed to record the delivery of {}: {}", kind, err);
+                    return;
+                }
+            }
+        }
+
+        let mut messages = messages.into_iter();
+        let sent = match messages.next() {
+            Some(text) => self.send(bot, pool, chat_id, &text).await,
+            None => false,
+        };
+
+        if sent {
+            for text in messages {
+                self.send(bot, pool, chat_id, &text).await;
+            }
+        } else if let Some((kind, logical_date)) = &delivery
+            && let Err(err) = release_delivery(pool, chat_id.0, kind, *logical_date).await
+        {
+            log::error!("Failed to release the delivery to {}: {}", chat_id, err);
+        }
+    }
+
+    /// Delivers the notifications to their users, spread over the window of the dispatcher
+    /// if it has one
+    pub async fn broadcast(
+        &self,
+        bot: &Bot,
+        pool: &Pool<Postgres>,
+        notifications: Vec<Notification>,
+    ) {
+        let len = notifications.len();
+        if len == 0 {
+            return;
+        }
+
+        let started_at = tokio::time::Instant::now();
+        let sending = Arc::new(Semaphore::new(MAX_CONCURRENT_DELIVERIES));
+        let mut handles = Vec::with_capacity(len);
+
+        for (index, notification) in notifications.into_iter().enumerate() {
+            if let Some(spread) = self.spread {
+                tokio::time::sleep_until(started_at + spread.mul_f64(index as f64 / len as f64))
+                    .await;
+            }
+
+            let Ok(permit) = Arc::clone(&sending).acquire_owned().await else {
+                break;
+            };
+
+            let dispatcher = self.clone();
+            let bot = bot.clone();
+            let pool = pool.clone();
+            handles.push(tokio::spawn(async move {
+                dispatcher.deliver(&bot, &pool, notification).await;
+                drop(permit);
+            }));
+        }
+
+        for handle in handles {
+            if let Err(err) = handle.await {
+                log::error!("Failed to deliver a notification: {}", err);
+            }
+        }
+
+        log::info!("Broadcast of {} notifications done", len);
+    }
+}
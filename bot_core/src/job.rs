@@ -0,0 +1,153 @@
+use std::{collections::HashMap, pin::Pin};
+
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, types::Uuid};
+use tokio_cron_scheduler::{
+    Job, JobScheduler, JobSchedulerError, job::job_data_prost::JobStoredData,
+};
+
+use crate::db::tables;
+
+/// Creates a job running once at the given moment, with the extra data stored along with it.
+///
+/// The cron jobs run on a fixed schedule, so the jobs whose time moves every day (e.g. the ones
+/// relative to the prayer times) run as a chain of one shot jobs, each computing its next tick
+/// and adding the job for it before it finishes.
+pub fn one_shot_job_at<T>(
+    at: DateTime<Utc>,
+    extra: Vec<u8>,
+    run: T,
+) -> Result<Job, JobSchedulerError>
+where
+    T: FnMut(Uuid, JobScheduler) -> Pin<Box<dyn Future<Output = ()> + Send>>
+        + Send
+        + Sync
+        + 'static,
+{
+    let delay = (at - Utc::now()).to_std().unwrap_or_default();
+    let mut job = Job::new_one_shot_async(delay, run)?;
+    let job_data = job.job_data()?;
+
+    job.set_job_data(JobStoredData {
+        next_tick: at.timestamp().max(Utc::now().timestamp()) as u64,
+        extra,
+        ..job_data
+    })?;
+
+    Ok(job)
+}
+
+/// Builds the job of a persisted job from its stored data, or `None` when it can't run anymore
+pub type JobFactory = Box<dyn Fn(&JobStoredData) -> Option<Job> + Send + Sync>;
+
+/// Gives the persisted jobs their code back after a restart.
+///
+/// The store keeps the data of the jobs, but their code only lives in memory. On startup the
+/// rehydrator reads the jobs along with their extension types, builds each one again with the
+/// factory of its type and adds it to the scheduler under its original id.
+pub struct JobRehydrator {
+    pool: Pool<Postgres>,
+    factories: HashMap<i32, JobFactory>,
+}
+
+impl JobRehydrator {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self {
+            pool,
+            factories: HashMap::new(),
+        }
+    }
+
+    pub fn with_factory<F>(mut self, extension_type: i32, factory: F) -> Self
+    where
+        F: Fn(&JobStoredData) -> Option<Job> + Send + Sync + 'static,
+    {
+        self.factories.insert(extension_type, Box::new(factory));
+        self
+    }
+
+    /// Adds the persisted jobs to the scheduler and returns how many of them were added. The
+    /// jobs the factories can't build are removed, as nothing would run them.
+    pub async fn run(&self, sched: &JobScheduler) -> Result<usize, JobSchedulerError> {
+        let rows = sqlx::query!(
+            r#"
+                SELECT
+                    jobs.id,
+                    jobs.last_updated,
+                    jobs.next_tick,
+                    jobs.last_tick,
+                    jobs.job_type,
+                    jobs.count,
+                    jobs.ran,
+                    jobs.stopped,
+                    jobs.time_offset_seconds,
+                    jobs.extra,
+                    jobs.schedule,
+                    jobs.repeated_every,
+                    jobs.repeating,
+                    job_extensions.type AS extension_type
+                FROM jobs
+                JOIN job_extensions ON job_extensions.job_id = jobs.id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to fetch the persisted jobs: {}", e);
+            JobSchedulerError::FetchJob
+        })?;
+
+        let mut rehydrated = 0;
+
+        for row in rows {
+            let Some(factory) = self.factories.get(&row.extension_type) else {
+                log::warn!(
+                    "No factory for the job {} of type {}, skipping it",
+                    row.id,
+                    row.extension_type
+                );
+                continue;
+            };
+
+            let stored_data: Option<JobStoredData> = tables::Job {
+                id: row.id,
+                last_updated: row.last_updated,
+                next_tick: row.next_tick,
+                last_tick: row.last_tick,
+                job_type: row.job_type,
+                count: row.count,
+                ran: row.ran,
+                stopped: row.stopped,
+                time_offset_seconds: row.time_offset_seconds,
+                extra: row.extra,
+                schedule: row.schedule,
+                repeated_every: row.repeated_every,
+                repeating: row.repeating,
+            }
+            .into();
+
+            let Some(mut job) = stored_data.as_ref().and_then(factory) else {
+                log::warn!("Failed to rebuild the job {}, removing it", row.id);
+                if let Err(err) = sched.remove(&row.id).await {
+                    log::error!("Failed to remove the job {}: {}", row.id, err);
+                }
+                continue;
+            };
+
+            let job_data = job.job_data()?;
+            job.set_job_data(JobStoredData {
+                id: Some(row.id.into()),
+                ..job_data
+            })?;
+
+            match sched.add(job).await {
+                Ok(_) => rehydrated += 1,
+                Err(err) => log::error!("Failed to add the job {}: {}", row.id, err),
+            }
+        }
+
+        log::info!("Rehydrated {} persisted jobs", rehydrated);
+
+        Ok(rehydrated)
+    }
+}
@@ -1,2 +1,5 @@
 pub mod bot_core;
 pub mod db;
+pub mod delivery_time;
+pub mod dispatcher;
+pub mod job;
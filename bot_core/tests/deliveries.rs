@@ -0,0 +1,52 @@
+use bot_core::db::{
+    deliveries::{DeliveryClaim, claim_delivery, release_delivery},
+    migrator::{MigrationProject, Migrator},
+};
+use chrono::{NaiveDate, Utc};
+use sqlx::{Pool, Postgres, postgres::PgPoolOptions, types::Uuid};
+
+/// The database of the Hijri bot, when `DATABASE_URL` points to one
+async fn pool() -> Option<Pool<Postgres>> {
+    let database_url = std::env::var("DATABASE_URL").ok()?;
+    Migrator::run(&database_url, MigrationProject::HijriEventBot)
+        .await
+        .expect("Failed to run the migrations");
+
+    Some(
+        PgPoolOptions::new()
+            .connect(&database_url)
+            .await
+            .expect("Failed to connect to the database"),
+    )
+}
+
+#[tokio::test]
+async fn claims_a_delivery_once_until_it_is_released() {
+    let Some(pool) = pool().await else {
+        eprintln!("DATABASE_URL is not set, skipping");
+        return;
+    };
+
+    let chat_id = Utc::now().timestamp_micros();
+    let logical_date = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+    sqlx::query("INSERT INTO users (id, chat_id) VALUES ($1, $2)")
+        .bind(Uuid::new_v4())
+        .bind(chat_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let claim = claim_delivery(&pool, chat_id, "white_days", logical_date).await;
+    assert!(matches!(claim, Ok(DeliveryClaim::Claimed)));
+    let claim = claim_delivery(&pool, chat_id, "white_days", logical_date).await;
+    assert!(matches!(claim, Ok(DeliveryClaim::AlreadyDelivered)));
+
+    release_delivery(&pool, chat_id, "white_days", logical_date)
+        .await
+        .unwrap();
+    let claim = claim_delivery(&pool, chat_id, "white_days", logical_date).await;
+    assert!(matches!(claim, Ok(DeliveryClaim::Claimed)));
+
+    let claim = claim_delivery(&pool, -chat_id, "white_days", logical_date).await;
+    assert!(matches!(claim, Ok(DeliveryClaim::UnknownUser)));
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use bot_core::dispatcher::{CHAT_INTERVAL, RateLimiter};
+use teloxide::types::ChatId;
+
+#[test]
+fn holds_the_messages_to_the_global_rate() {
+    let start = Instant::now();
+    let mut limiter = RateLimiter::new(30, start);
+
+    for chat_id in 0..30 {
+        assert_eq!(limiter.try_acquire(ChatId(chat_id), start), Ok(()));
+    }
+
+    let wait = limiter.try_acquire(ChatId(30), start).unwrap_err();
+    assert!(wait > Duration::ZERO && wait <= Duration::from_millis(34));
+
+    assert_eq!(limiter.try_acquire(ChatId(30), start + wait), Ok(()));
+}
+
+#[test]
+fn paces_the_messages_to_the_same_chat() {
+    let start = Instant::now();
+    let mut limiter = RateLimiter::new(30, start);
+
+    assert_eq!(limiter.try_acquire(ChatId(1), start), Ok(()));
+
+    let later = start + Duration::from_millis(400);
+    assert_eq!(
+        limiter.try_acquire(ChatId(1), later),
+        Err(CHAT_INTERVAL - Duration::from_millis(400))
+    );
+    assert_eq!(limiter.try_acquire(ChatId(2), later), Ok(()));
+
+    assert_eq!(
+        limiter.try_acquire(ChatId(1), start + CHAT_INTERVAL),
+        Ok(())
+    );
+}
+
+#[test]
+fn waits_for_as_long_as_telegram_asked() {
+    let start = Instant::now();
+    let mut limiter = RateLimiter::new(30, start);
+
+    limiter.pause(start + Duration::from_secs(5));
+
+    assert_eq!(
+        limiter.try_acquire(ChatId(1), start + Duration::from_secs(2)),
+        Err(Duration::from_secs(3))
+    );
+    assert_eq!(
+        limiter.try_acquire(ChatId(1), start + Duration::from_secs(5)),
+        Ok(())
+    );
+}
@@ -0,0 +1,9 @@
+-- Add migration script here
+
+ALTER TABLE users
+ADD COLUMN delivery_time TIME NOT NULL DEFAULT '08:00';
+
+-- The daily hadith job is recreated to tick every minute and send to the users whose
+-- delivery time it is
+DELETE FROM jobs
+WHERE id IN (SELECT job_id FROM job_extensions WHERE type = 1);
@@ -0,0 +1,4 @@
+-- Add migration script here
+
+ALTER TABLE users
+ADD COLUMN active BOOL NOT NULL DEFAULT TRUE;
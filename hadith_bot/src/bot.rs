@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use bot_core::bot_core::BotCore;
+use bot_core::{bot_core::BotCore, db::users::deactivate_user, delivery_time::parse_delivery_time};
 use sqlx::{Pool, Postgres};
 use teloxide::{
     ApiError, Bot, RequestError,
@@ -39,6 +39,78 @@ impl TelegramBot {
         .await;
     }
 
+    /// Shows the time at which the user receives the daily hadith, or changes it. The times
+    /// are in UTC, in which the daily hadith job runs.
+    async fn change_delivery_time(bot: &Bot, chat_id: ChatId, args: &str, pool: &Pool<Postgres>) {
+        let args = args.trim();
+
+        if args.is_empty() {
+            let text = match sqlx::query_scalar!(
+                "
+                    SELECT delivery_time
+                    FROM users
+                    WHERE chat_id = $1
+                ",
+                chat_id.0
+            )
+            .fetch_optional(pool)
+            .await
+            {
+                Ok(Some(delivery_time)) => format!(
+                    "Dnevni hadis primate svakog dana u {} (UTC).\n\nZa promjenu pošaljite npr. /vrijeme 07:30.",
+                    delivery_time.format("%H:%M")
+                ),
+                Ok(None) => "Za primanje dnevnog hadisa pošaljite /start.".to_string(),
+                Err(e) => {
+                    log::error!("Failed to fetch the delivery time of {}: {}", chat_id, e);
+                    "Dogodila se greška. Pokušajte kasnije.".to_string()
+                }
+            };
+            BotCore::send_message(bot, chat_id, text).await;
+            return;
+        }
+
+        let Some(delivery_time) = parse_delivery_time(args) else {
+            BotCore::send_message(
+                bot,
+                chat_id,
+                format!(
+                    "Vrijeme \"{}\" nije ispravno. Pošaljite ga u obliku sati:minute, npr. /vrijeme 07:30.",
+                    args
+                ),
+            )
+            .await;
+            return;
+        };
+
+        let text = match sqlx::query!(
+            "
+                INSERT INTO users (chat_id, delivery_time)
+                VALUES ($1, $2)
+                ON CONFLICT (chat_id)
+                DO UPDATE SET delivery_time = EXCLUDED.delivery_time
+            ",
+            chat_id.0,
+            delivery_time
+        )
+        .execute(pool)
+        .await
+        {
+            Ok(_) => {
+                log::info!("Delivery time of {} set to {}", chat_id, delivery_time);
+                format!(
+                    "Dnevni hadis ćete od sada primati u {} (UTC).",
+                    delivery_time.format("%H:%M")
+                )
+            }
+            Err(e) => {
+                log::error!("Failed to set the delivery time of {}: {}", chat_id, e);
+                "Dogodila se greška. Pokušajte kasnije.".to_string()
+            }
+        };
+        BotCore::send_message(bot, chat_id, text).await;
+    }
+
     pub async fn run(&self) {
         log::info!("Starting Hadith bot...");
 
@@ -58,7 +130,7 @@ impl TelegramBot {
                         BotCore::send_message(
                             &bot,
                             msg.chat.id,
-                            "Dostupne komande:\n\n/help - Prikaži postojeće komande\n/hadis - Prikaži nasumični hadis".to_string(),
+                            "Dostupne komande:\n\n/help - Prikaži postojeće komande\n/hadis - Prikaži nasumični hadis\n/vrijeme - Prikaži ili promijeni vrijeme dnevnog hadisa, npr. /vrijeme 07:30\n/stop - Prestani primati dnevni hadis".to_string(),
                         )
                         .await;
                     }
@@ -70,7 +142,7 @@ impl TelegramBot {
                                 INSERT INTO users (chat_id) 
                                 VALUES ($1)
                                 ON CONFLICT (chat_id)
-                                DO UPDATE SET chat_id = EXCLUDED.chat_id
+                                DO UPDATE SET active = TRUE
                                 RETURNING id
                             ",
                             msg.chat.id.0 as i64,
@@ -83,6 +155,19 @@ impl TelegramBot {
                         })?;
                         TelegramBot::send_welcome_message(bot, msg.chat.id).await;
                     }
+                    Command::Vrijeme(args) => {
+                        TelegramBot::change_delivery_time(&bot, msg.chat.id, &args, &pool).await;
+                    }
+                    Command::Stop => {
+                        deactivate_user(&pool, msg.chat.id.0).await;
+                        BotCore::send_message(
+                            &bot,
+                            msg.chat.id,
+                            "Više nećete primati dnevni hadis. Za ponovno primanje pošaljite /start."
+                                .to_string(),
+                        )
+                        .await;
+                    }
                     Command::Hadis => match hadith_repo.get_random_hadith_text().await {
                         Ok(hadith) => {
                             BotCore::send_message(&bot, msg.chat.id, hadith).await;
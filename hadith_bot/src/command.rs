@@ -5,6 +5,8 @@ use teloxide::macros::BotCommands;
 pub enum Command {
     Help,
     Hadis,
+    Vrijeme(String),
+    Stop,
     #[command(hide)]
     Start,
 }
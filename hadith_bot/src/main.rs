@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use bot_core::dispatcher::NotificationDispatcher;
 use teloxide::Bot;
 
 use crate::{bot::TelegramBot, error::AppErrorKind, scheduler::Scheduler};
@@ -43,12 +44,23 @@ async fn main() -> Result<(), AppErrorKind> {
             log::error!("Failed to connect to the database: {}", err);
             AppErrorKind::DatabaseConnectionError
         })?;
-    let scheduler = Scheduler::new(pool.clone()).await?;
+    let scheduler = Scheduler::new(pool.clone())
+        .await?
+        .with_dispatcher(NotificationDispatcher::from_env());
     let bot_from_env = Bot::from_env();
     let bot = TelegramBot::new(pool.clone(), bot_from_env.clone());
+    let pool = Arc::new(pool);
     scheduler
-        .schedule_daily_hadith_job(bot_from_env, Arc::new(pool))
+        .rehydrate_jobs(bot_from_env.clone(), Arc::clone(&pool))
         .await?;
+    scheduler
+        .schedule_daily_hadith_job(bot_from_env.clone(), Arc::clone(&pool))
+        .await?;
+
+    let catch_up_scheduler = scheduler.clone();
+    tokio::spawn(async move {
+        catch_up_scheduler.catch_up(bot_from_env, pool).await;
+    });
 
     bot.run().await;
 
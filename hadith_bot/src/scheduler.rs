@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
 use bot_core::{
-    bot_core::BotCore,
     db::{
         postgres_metadata_store::{JobCallbacksExtension, PostgresMetadataStore},
         postgres_notification_store::PostgresNotificationStore,
     },
+    dispatcher::{Notification, NotificationDispatcher},
+    job::JobRehydrator,
 };
+use chrono::{NaiveDate, Timelike, Utc};
 use sqlx::{Pool, Postgres};
 use teloxide::{Bot, types::ChatId};
 use tokio_cron_scheduler::{
@@ -19,9 +21,20 @@ use crate::{
     job::{JobExtensionType, JobExtraData},
 };
 
+/// Delivery times are whole minutes in UTC, checked by the daily hadith job every minute
+const DAILY_HADITH_CHECK_SCHEDULE: &str = "0 * * * * *";
+
+/// Kind of the daily hadith in the `deliveries` table
+const DAILY_HADITH_DELIVERY_KIND: &str = "daily_hadith";
+
+/// Hours back in which the daily hadith missed while the bot was down is still sent on startup
+const CATCH_UP_HOURS: i64 = 3;
+
+#[derive(Clone)]
 pub struct Scheduler {
     sched: JobScheduler,
     hadith_repo: Arc<HadithRepository>,
+    dispatcher: NotificationDispatcher,
 }
 
 struct SchedulerCallbacks;
@@ -46,7 +59,11 @@ impl JobCallbacksExtension for SchedulerCallbacks {
                 })?;
 
             sqlx::query!(
-                "INSERT INTO job_extensions (job_id, type) VALUES ($1, $2)",
+                "
+                    INSERT INTO job_extensions (job_id, type)
+                    VALUES ($1, $2)
+                    ON CONFLICT (job_id) DO NOTHING
+                ",
                 job.id,
                 extra_job_data.extension_type as i32
             )
@@ -100,17 +117,21 @@ impl Scheduler {
         Ok(Self {
             sched,
             hadith_repo: Arc::new(HadithRepository::new(pool)),
+            dispatcher: NotificationDispatcher::new(),
         })
     }
 
+    /// Dispatcher the daily hadith is sent through, within the limits of Telegram
+    pub fn with_dispatcher(mut self, dispatcher: NotificationDispatcher) -> Self {
+        self.dispatcher = dispatcher;
+        self
+    }
+
     pub async fn schedule_daily_hadith_job(
         &self,
         bot: Bot,
         pool: Arc<Pool<Postgres>>,
     ) -> Result<(), AppErrorKind> {
-        let bot = Arc::new(bot);
-        let hadith_repo = Arc::clone(&self.hadith_repo);
-
         let job_with_type_exists = sqlx::query_scalar!(
             "
                 SELECT EXISTS (
@@ -136,66 +157,61 @@ impl Scheduler {
             return Ok(());
         }
 
-        let mut daily_hadith_job = Job::new_async("0 0 8 * * *", move |_uuid, _l| {
+        let daily_hadith_job = self.daily_hadith_job(Arc::new(bot), pool)?;
+
+        self.sched.add(daily_hadith_job).await.map_err(|err| {
+            log::error!("Failed to schedule daily hadith job: {}", err);
+            AppErrorKind::ScheduleDailyHadithJob
+        })?;
+
+        Ok(())
+    }
+
+    fn daily_hadith_job(
+        &self,
+        bot: Arc<Bot>,
+        pool: Arc<Pool<Postgres>>,
+    ) -> Result<Job, AppErrorKind> {
+        let scheduler = self.clone();
+
+        // The job ticks every minute and sends the hadith to the users whose delivery time it
+        // is, so a changed delivery time takes effect from the next tick
+        let mut daily_hadith_job = Job::new_async(DAILY_HADITH_CHECK_SCHEDULE, move |_uuid, _l| {
+            let scheduler = scheduler.clone();
             let bot = bot.clone();
-            let hadith_repo = hadith_repo.clone();
             let pool = pool.clone();
 
             Box::pin(async move {
-                let hadith_repo = hadith_repo.clone();
-                let bot = bot.clone();
-
-                let chat_handles_res = sqlx::query!("SELECT chat_id FROM users")
-                    .fetch_all(&*pool)
-                    .await
-                    .map_err(|e| {
-                        log::error!("Failed to fetch users: {}", e);
-                        AppErrorKind::SendDailyHadithMessage
-                    })
-                    .map(|rows| {
-                        let rows: Vec<i64> = rows.into_iter().map(|row| row.chat_id).collect();
-
-                        rows.into_iter().map(|chat_id| {
-                            let hadith_repo = hadith_repo.clone();
-                            let bot = bot.clone();
-
-                            tokio::spawn(async move {
-                                let chat_id = ChatId(chat_id);
-                                match hadith_repo.get_random_hadith_text().await {
-                                    Ok(hadith) => {
-                                        BotCore::send_message(&bot, chat_id, hadith).await;
-                                    }
-                                    Err(e) => {
-                                        log::error!("Failed to fetch daily hadith: {}", e);
-                                        BotCore::send_message(
-                                            &bot,
-                                            chat_id,
-                                            "Dogodila se greška. Pokušajte ponovo kasnije."
-                                                .to_string(),
-                                        )
-                                        .await;
-                                    }
-                                }
-                            })
-                        })
-                    });
-
-                match chat_handles_res {
-                    Ok(handles) => {
-                        let len = handles.len();
-
-                        for handle in handles {
-                            if let Err(e) = handle.await {
-                                log::error!("Error in daily hadith job: {}", e);
-                            }
-                        }
-
-                        log::info!("Successfully sent {} daily hadith messages", len);
-                    }
+                let now = Utc::now();
+                let Some(delivery_time) = now
+                    .time()
+                    .with_second(0)
+                    .and_then(|time| time.with_nanosecond(0))
+                else {
+                    return;
+                };
+
+                let recipients = match sqlx::query!(
+                    "SELECT chat_id FROM users WHERE active AND delivery_time = $1",
+                    delivery_time
+                )
+                .fetch_all(&*pool)
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to fetch users: {}", e);
+                    AppErrorKind::SendDailyHadithMessage
+                }) {
+                    Ok(rows) => rows
+                        .into_iter()
+                        .map(|row| (row.chat_id, now.date_naive()))
+                        .collect(),
                     Err(e) => {
                         log::error!("Error fetching chat handles: {}", e);
+                        return;
                     }
-                }
+                };
+
+                scheduler.send_daily_hadith(bot, pool, recipients).await;
             })
         })
         .map_err(|err| {
@@ -226,9 +242,102 @@ impl Scheduler {
                 AppErrorKind::ScheduleDailyHadithJob
             })?;
 
-        self.sched.add(daily_hadith_job).await.map_err(|err| {
-            log::error!("Failed to schedule daily hadith job: {}", err);
-            AppErrorKind::ScheduleDailyHadithJob
+        Ok(daily_hadith_job)
+    }
+
+    /// Sends the daily hadith to each user for the UTC date of the delivery, unless the user
+    /// has already received it
+    async fn send_daily_hadith(
+        &self,
+        bot: Arc<Bot>,
+        pool: Arc<Pool<Postgres>>,
+        recipients: Vec<(i64, NaiveDate)>,
+    ) {
+        let mut notifications = Vec::with_capacity(recipients.len());
+
+        for (chat_id, date) in recipients {
+            // The user is skipped without a record of the delivery, so the catch up on the next
+            // start can send the hadith
+            let text = match self.hadith_repo.get_random_hadith_text().await {
+                Ok(hadith) => hadith,
+                Err(e) => {
+                    log::error!("Failed to fetch daily hadith for {}: {}", chat_id, e);
+                    continue;
+                }
+            };
+
+            notifications.push(
+                Notification::new(ChatId(chat_id), vec![text])
+                    .once(DAILY_HADITH_DELIVERY_KIND, date),
+            );
+        }
+
+        self.dispatcher.broadcast(&bot, &pool, notifications).await;
+    }
+
+    /// Sends the daily hadith to the users whose delivery time was in the last
+    /// [`CATCH_UP_HOURS`] hours and who haven't received it, e.g. because the bot was down
+    pub async fn catch_up(&self, bot: Bot, pool: Arc<Pool<Postgres>>) {
+        let users = match sqlx::query!(
+            "
+                SELECT chat_id, delivery_time
+                FROM users
+                WHERE active
+            "
+        )
+        .fetch_all(&*pool)
+        .await
+        {
+            Ok(users) => users,
+            Err(e) => {
+                log::error!("Failed to fetch users to catch up: {}", e);
+                return;
+            }
+        };
+
+        let now = Utc::now().naive_utc();
+        let recipients = users
+            .into_iter()
+            .filter_map(|user| {
+                let today = now.date().and_time(user.delivery_time);
+                let delivery = if today <= now {
+                    today
+                } else {
+                    today - chrono::Duration::days(1)
+                };
+
+                (now - delivery < chrono::Duration::hours(CATCH_UP_HOURS))
+                    .then_some((user.chat_id, delivery.date()))
+            })
+            .collect();
+
+        self.send_daily_hadith(Arc::new(bot), pool, recipients)
+            .await;
+
+        log::info!("Missed daily hadith messages caught up");
+    }
+
+    /// Adds the jobs persisted before a restart back to the scheduler under their ids, as only
+    /// their data outlives the process
+    pub async fn rehydrate_jobs(
+        &self,
+        bot: Bot,
+        pool: Arc<Pool<Postgres>>,
+    ) -> Result<(), AppErrorKind> {
+        let scheduler = self.clone();
+        let bot = Arc::new(bot);
+        let rehydrator = JobRehydrator::new((*pool).clone()).with_factory(
+            JobExtensionType::DailyHadithMessage as i32,
+            move |_data| {
+                scheduler
+                    .daily_hadith_job(Arc::clone(&bot), Arc::clone(&pool))
+                    .ok()
+            },
+        );
+
+        rehydrator.run(&self.sched).await.map_err(|err| {
+            log::error!("Failed to rehydrate the persisted jobs: {}", err);
+            AppErrorKind::SchedulerInitialization
         })?;
 
         Ok(())
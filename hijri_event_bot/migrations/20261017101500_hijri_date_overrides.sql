@@ -0,0 +1,12 @@
+-- Add migration script here
+
+CREATE TABLE IF NOT EXISTS hijri_date_overrides (
+    gregorian_date DATE NOT NULL,
+    hijri_year INTEGER NOT NULL,
+    hijri_month SMALLINT NOT NULL,
+    hijri_day SMALLINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CONSTRAINT pk_hijri_date_override PRIMARY KEY (gregorian_date),
+    CONSTRAINT chk_hijri_month CHECK (hijri_month BETWEEN 1 AND 12),
+    CONSTRAINT chk_hijri_day CHECK (hijri_day BETWEEN 1 AND 30)
+);
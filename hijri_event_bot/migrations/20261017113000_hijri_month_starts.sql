@@ -0,0 +1,11 @@
+-- Add migration script here
+
+CREATE TABLE IF NOT EXISTS hijri_month_starts (
+    hijri_year INTEGER NOT NULL,
+    hijri_month SMALLINT NOT NULL,
+    gregorian_date DATE NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CONSTRAINT pk_hijri_month_start PRIMARY KEY (hijri_year, hijri_month),
+    CONSTRAINT uq_hijri_month_start_gregorian_date UNIQUE (gregorian_date),
+    CONSTRAINT chk_hijri_month_start_month CHECK (hijri_month BETWEEN 1 AND 12)
+);
@@ -0,0 +1,12 @@
+-- Add migration script here
+
+CREATE TABLE IF NOT EXISTS hijri_date_cache (
+    gregorian_date DATE NOT NULL,
+    timezone TEXT NOT NULL,
+    method TEXT NOT NULL,
+    hijri_year INTEGER NOT NULL,
+    hijri_month SMALLINT NOT NULL,
+    hijri_day SMALLINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CONSTRAINT pk_hijri_date_cache PRIMARY KEY (gregorian_date, timezone, method)
+);
@@ -0,0 +1,4 @@
+-- Add migration script here
+
+ALTER TABLE users
+ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'Europe/Sarajevo';
@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE users
+    ADD COLUMN latitude DOUBLE PRECISION NULL,
+    ADD COLUMN longitude DOUBLE PRECISION NULL;
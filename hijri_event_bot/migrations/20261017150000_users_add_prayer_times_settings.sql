@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE users
+    ADD COLUMN prayer_times_method VARCHAR(32) NULL,
+    ADD COLUMN asr_method VARCHAR(16) NULL;
@@ -0,0 +1,9 @@
+-- Add migration script here
+
+CREATE TABLE IF NOT EXISTS prayer_reminders (
+    user_id UUID NOT NULL,
+    prayer VARCHAR(16) NOT NULL,
+    minutes_before INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT pk_prayer_reminders PRIMARY KEY (user_id, prayer),
+    CONSTRAINT fk_user_id FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,9 @@
+-- Add migration script here
+
+CREATE TABLE IF NOT EXISTS shawwal_fasts (
+    user_id UUID NOT NULL,
+    year INTEGER NOT NULL,
+    days INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT pk_shawwal_fasts PRIMARY KEY (user_id, year),
+    CONSTRAINT fk_user_id FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+-- Add migration script here
+
+ALTER TABLE users
+ADD COLUMN delivery_time TIME;
@@ -0,0 +1,50 @@
+-- Add migration script here
+
+ALTER TABLE users_jobs
+ADD COLUMN extension_type INTEGER;
+
+UPDATE users_jobs
+SET extension_type = job_extensions.type
+FROM job_extensions
+WHERE job_extensions.job_id = users_jobs.job_id;
+
+DELETE FROM users_jobs
+WHERE extension_type IS NULL;
+
+-- Each /start used to add another white days job, so only the first job of each type of a user
+-- is kept
+DELETE FROM jobs
+WHERE id IN (
+    SELECT ranked.job_id
+    FROM (
+        SELECT
+            users_jobs.job_id,
+            ROW_NUMBER() OVER (
+                PARTITION BY users_jobs.user_id, users_jobs.extension_type
+                ORDER BY jobs.last_updated, jobs.id
+            ) AS position
+        FROM users_jobs
+        JOIN jobs ON jobs.id = users_jobs.job_id
+    ) AS ranked
+    WHERE ranked.position > 1
+);
+
+-- The white days jobs added before the jobs were linked to their users can't be told apart,
+-- and no code runs them after a restart
+DELETE FROM jobs
+WHERE id IN (
+    SELECT job_extensions.job_id
+    FROM job_extensions
+    WHERE job_extensions.type = 1
+    AND NOT EXISTS (
+        SELECT 1
+        FROM users_jobs
+        WHERE users_jobs.job_id = job_extensions.job_id
+    )
+);
+
+ALTER TABLE users_jobs
+ALTER COLUMN extension_type SET NOT NULL;
+
+ALTER TABLE users_jobs
+ADD CONSTRAINT uq_users_jobs_user_extension_type UNIQUE (user_id, extension_type);
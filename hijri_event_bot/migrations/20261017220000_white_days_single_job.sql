@@ -0,0 +1,10 @@
+-- Add migration script here
+
+-- The white days message is sent by a single job to all the users, which is added on startup
+-- in place of the job each user had
+DELETE FROM jobs
+WHERE id IN (
+    SELECT job_extensions.job_id
+    FROM job_extensions
+    WHERE job_extensions.type = 1
+);
@@ -0,0 +1,10 @@
+-- Add migration script here
+
+CREATE TABLE IF NOT EXISTS deliveries (
+    user_id UUID NOT NULL,
+    kind VARCHAR(64) NOT NULL,
+    logical_date DATE NOT NULL,
+    delivered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT pk_deliveries PRIMARY KEY (user_id, kind, logical_date),
+    CONSTRAINT fk_user_id FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
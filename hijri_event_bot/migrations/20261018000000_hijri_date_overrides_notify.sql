@@ -0,0 +1,24 @@
+-- Add migration script here
+
+-- Announces the first date changed by every edit of the overrides, so the bot drops the cached
+-- answers from it on
+CREATE OR REPLACE FUNCTION notify_hijri_date_override() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM pg_notify('hijri_date_overrides', NEW.gregorian_date::TEXT);
+    ELSIF TG_OP = 'DELETE' THEN
+        PERFORM pg_notify('hijri_date_overrides', OLD.gregorian_date::TEXT);
+    ELSE
+        PERFORM pg_notify(
+            'hijri_date_overrides',
+            LEAST(OLD.gregorian_date, NEW.gregorian_date)::TEXT
+        );
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER hijri_date_overrides_changed
+AFTER INSERT OR UPDATE OR DELETE ON hijri_date_overrides
+FOR EACH ROW EXECUTE FUNCTION notify_hijri_date_override();
@@ -0,0 +1,8 @@
+-- Add migration script here
+
+-- An answer depends only on the Gregorian date, whichever provider gave it
+TRUNCATE hijri_date_cache;
+
+ALTER TABLE hijri_date_cache DROP CONSTRAINT pk_hijri_date_cache;
+ALTER TABLE hijri_date_cache DROP COLUMN timezone, DROP COLUMN method;
+ALTER TABLE hijri_date_cache ADD CONSTRAINT pk_hijri_date_cache PRIMARY KEY (gregorian_date);
@@ -1,10 +1,14 @@
 use std::sync::Arc;
 
+use chrono::NaiveDate;
 use serde::{Deserialize, de::DeserializeOwned};
 
 use crate::{
+    calendar::{HijriYmd, arabic_month_name},
     error::AppErrorKind,
+    http::HttpClient,
     i18n::{instance::I18n, translation_key::TranslationKey},
+    provider::{HijriDateProvider, ProviderFuture},
 };
 
 #[derive(Deserialize)]
@@ -30,6 +34,22 @@ pub struct HijriApiResponse {
     pub data: HijriData,
 }
 
+#[derive(Deserialize)]
+pub struct GregorianDate {
+    /// Formatted as `DD-MM-YYYY`
+    pub date: String,
+}
+
+#[derive(Deserialize)]
+pub struct GregorianData {
+    pub gregorian: GregorianDate,
+}
+
+#[derive(Deserialize)]
+pub struct GregorianApiResponse {
+    pub data: GregorianData,
+}
+
 fn pad_left(value: &str, width: usize) -> String {
     let mut padded = value.to_string();
     while padded.len() < width {
@@ -38,6 +58,7 @@ fn pad_left(value: &str, width: usize) -> String {
     padded
 }
 
+#[derive(Clone)]
 pub struct CurrentDateResponse {
     pub day: String,
     pub day_number: u8,
@@ -49,7 +70,7 @@ pub struct CurrentDateResponse {
 }
 
 impl CurrentDateResponse {
-    fn map_translated_month(month: u8, i18n: &I18n) -> String {
+    pub fn map_translated_month(month: u8, i18n: &I18n) -> String {
         match month {
             1 => i18n.t(&TranslationKey::MonthMuharram),
             2 => i18n.t(&TranslationKey::MonthSafar),
@@ -81,6 +102,24 @@ impl CurrentDateResponse {
             month_number: hijri_data.data.hijri.month.number,
         }
     }
+
+    pub fn from_hijri_ymd(hijri_date: &HijriYmd, i18n: &I18n) -> Self {
+        Self {
+            day: pad_left(&hijri_date.day.to_string(), 2),
+            month: pad_left(&hijri_date.month.to_string(), 2),
+            year: hijri_date.year.to_string(),
+            month_name: CurrentDateResponse::map_translated_month(hijri_date.month, i18n),
+            month_ar: arabic_month_name(hijri_date.month).to_string(),
+            day_number: hijri_date.day,
+            month_number: hijri_date.month,
+        }
+    }
+
+    pub fn is_plausible(&self) -> bool {
+        (1..=30).contains(&self.day_number)
+            && (1..=12).contains(&self.month_number)
+            && self.year.parse::<i32>().is_ok_and(|year| year > 0)
+    }
 }
 
 impl std::fmt::Display for CurrentDateResponse {
@@ -96,49 +135,70 @@ impl std::fmt::Display for CurrentDateResponse {
 pub struct HijriApi {
     api_url: String,
     i18n: Arc<I18n>,
+    http_client: HttpClient,
 }
 
 impl HijriApi {
-    pub fn new(i18n: Arc<I18n>) -> Self {
+    pub fn new(i18n: Arc<I18n>, http_client: HttpClient) -> Self {
         Self {
             api_url: "https://api.aladhan.com/v1".to_string(),
             i18n,
+            http_client,
         }
     }
 
+    /// Sends the requests to another Aladhan-compatible server, e.g. a mirror or a local stand-in
+    pub fn with_base_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
     async fn do_request<T>(&self, route: &str) -> Result<T, AppErrorKind>
     where
         T: DeserializeOwned,
     {
-        let response = reqwest::get(format!("{}{}", self.api_url, route))
+        self.http_client
+            .get_json(&format!("{}{}", self.api_url, route))
             .await
-            .map_err(|err| {
-                log::error!("API request failed: {}", err);
-                AppErrorKind::ApiRequest
-            })?;
+    }
+
+    pub async fn fetch_hijri_date(
+        &self,
+        date: NaiveDate,
+    ) -> Result<CurrentDateResponse, AppErrorKind> {
+        let hijri_data = self
+            .do_request::<HijriApiResponse>(&format!("/gToH/{}", date.format("%d-%m-%Y")))
+            .await?;
+
+        Ok(CurrentDateResponse::new(hijri_data, &self.i18n))
+    }
+
+    pub async fn fetch_gregorian_date(&self, date: &HijriYmd) -> Result<NaiveDate, AppErrorKind> {
+        let gregorian_data = self
+            .do_request::<GregorianApiResponse>(&format!(
+                "/hToG/{:02}-{:02}-{}",
+                date.day, date.month, date.year
+            ))
+            .await?;
 
-        if !response.status().is_success() {
+        NaiveDate::parse_from_str(&gregorian_data.data.gregorian.date, "%d-%m-%Y").map_err(|e| {
             log::error!(
-                "API request failed with status: {} for route: {}",
-                response.status(),
-                route
+                "Unexpected Gregorian date {} for {}: {}",
+                gregorian_data.data.gregorian.date,
+                date,
+                e
             );
-            return Err(AppErrorKind::ApiRequest);
-        }
-
-        response.json::<T>().await.map_err(|err| {
-            log::error!("Failed to parse response: {}", err);
-            AppErrorKind::ApiRequest
+            AppErrorKind::ApiInvalidResponse
         })
     }
+}
 
-    pub async fn get_current_hijri_date(&self) -> Result<CurrentDateResponse, AppErrorKind> {
-        let date_now = chrono::Utc::now().with_timezone(&chrono_tz::Tz::Europe__Sarajevo);
-
-        let hijri_data = self
-            .do_request::<HijriApiResponse>(&format!("/gToH/{}", date_now.format("%d-%m-%Y")))
-            .await?;
+impl HijriDateProvider for HijriApi {
+    fn name(&self) -> &'static str {
+        "aladhan"
+    }
 
-        Ok(CurrentDateResponse::new(hijri_data, &self.i18n))
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(self.fetch_hijri_date(date))
     }
 }
@@ -1,134 +1,1671 @@
 use std::{collections::HashMap, sync::Arc};
 
-use bot_core::bot_core::BotCore;
+use bot_core::{bot_core::BotCore, delivery_time::parse_delivery_time};
+use chrono::NaiveDate;
 use sqlx::{Pool, Postgres, types::Uuid};
-use teloxide::{ApiError, Bot, RequestError, repls::CommandReplExt, types::Message};
+use teloxide::{
+    ApiError, Bot, RequestError,
+    dispatching::{Dispatcher, HandlerExt, UpdateFilterExt},
+    dptree,
+    payloads::{
+        AnswerCallbackQuerySetters, EditMessageReplyMarkupSetters, EditMessageTextSetters,
+        SendMessageSetters,
+    },
+    prelude::Requester,
+    types::{
+        ButtonRequest, CallbackQuery, ChatId, InlineKeyboardButton, InlineKeyboardMarkup,
+        KeyboardButton, KeyboardMarkup, KeyboardRemove, Message, ParseMode, Update,
+    },
+};
 
 use crate::{
-    api::HijriApi,
+    api::CurrentDateResponse,
+    calendar::{HijriYmd, parse_month_name},
     command::Command,
+    convert::{DateConverter, DateQuery},
+    delivery_time::{get_user_delivery_time, set_user_delivery_time},
+    error::AppErrorKind,
+    event::{EventCatalog, IslamicEvent},
+    fasting::FastingRuling,
     i18n::{instance::I18n, translation_key::TranslationKey},
+    islamic_day::IslamicDay,
+    location::{Location, get_user_location, set_user_location},
+    month_view::MonthView,
+    notification::{NotificationCategory, get_disabled_notifications, set_notification_enabled},
+    prayer_reminder::{
+        PrayerReminder, get_prayer_reminders, remove_prayer_reminders, set_prayer_reminder,
+    },
+    prayer_times::{
+        AsrMethod, CalculationMethod, Prayer, PrayerTimes, PrayerTimesConfig,
+        get_user_prayer_times_config, set_user_prayer_times_config,
+    },
+    provider::{HijriDateProvider, month_start::MonthStartProvider},
     scheduler::Scheduler,
+    solar,
+    sunnah_fast::{SHAWWAL_FASTS, SunnahFast, add_shawwal_fasts},
+    timezone::{get_user_timezone, search_timezones, set_user_timezone},
 };
 
+/// Number of the upcoming events listed by `/events` without arguments
+const DEFAULT_EVENTS_COUNT: usize = 5;
+
+/// Prefix of the callback data of the notification settings buttons, followed by the category
+const SETTINGS_CALLBACK_PREFIX: &str = "settings:";
+
+/// Prefix of the callback data of the timezone buttons, followed by the IANA name
+const TIMEZONE_CALLBACK_PREFIX: &str = "timezone:";
+
+/// Most timezones offered as buttons, more matches have to be narrowed down by the user
+const MAX_TIMEZONE_CHOICES: usize = 8;
+
+struct MonthStartArgs {
+    month: u8,
+    year: i32,
+    start: NaiveDate,
+    broadcast: bool,
+}
+
+impl MonthStartArgs {
+    /// Parses arguments such as `ramazan 1448 2027-02-08 objavi`
+    fn parse(args: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = args.split_whitespace().collect();
+
+        let broadcast = parts
+            .last()
+            .is_some_and(|part| ["objavi", "broadcast"].contains(&part.to_lowercase().as_str()));
+        if broadcast {
+            parts.pop();
+        }
+
+        let start = parts.pop()?.trim_end_matches('.');
+        let start = NaiveDate::parse_from_str(start, "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(start, "%d.%m.%Y"))
+            .ok()?;
+        let year = parts.pop()?.trim_end_matches('.').parse().ok()?;
+        let month = parse_month_name(&parts.join(" "))?;
+
+        Some(Self {
+            month,
+            year,
+            start,
+            broadcast,
+        })
+    }
+}
+
 pub struct TelegramBot {
-    api: Arc<HijriApi>,
+    provider: Arc<dyn HijriDateProvider>,
+    converter: Arc<DateConverter>,
+    month_starts: Arc<MonthStartProvider>,
+    events: Arc<EventCatalog>,
     i18n: Arc<I18n>,
     bot: Bot,
     pool: Arc<Pool<Postgres>>,
     scheduler: Arc<Scheduler>,
+    prayer_times_config: PrayerTimesConfig,
+    admin_chat_ids: Arc<Vec<i64>>,
 }
 
 impl TelegramBot {
     pub fn new(
-        api: Arc<HijriApi>,
+        provider: Arc<dyn HijriDateProvider>,
+        converter: Arc<DateConverter>,
+        month_starts: Arc<MonthStartProvider>,
+        events: Arc<EventCatalog>,
         i18n: Arc<I18n>,
         pool: Pool<Postgres>,
         scheduler: Scheduler,
     ) -> Self {
+        let admin_chat_ids = std::env::var("ADMIN_CHAT_IDS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|chat_id| chat_id.trim().parse().ok())
+            .collect();
+
         Self {
-            api,
+            provider,
+            converter,
+            month_starts,
+            events,
             i18n,
             bot: Bot::from_env(),
             pool: Arc::new(pool),
             scheduler: Arc::new(scheduler),
+            prayer_times_config: PrayerTimesConfig::default(),
+            admin_chat_ids: Arc::new(admin_chat_ids),
+        }
+    }
+
+    /// Default prayer times settings of the users who haven't changed them
+    pub fn with_prayer_times_config(mut self, prayer_times_config: PrayerTimesConfig) -> Self {
+        self.prayer_times_config = prayer_times_config;
+        self
+    }
+
+    async fn announce_month_start(
+        bot: &Bot,
+        chat_id: ChatId,
+        args: &str,
+        provider: &dyn HijriDateProvider,
+        month_starts: &MonthStartProvider,
+        i18n: &I18n,
+        scheduler: &Scheduler,
+    ) {
+        let args = match MonthStartArgs::parse(args) {
+            Some(args) => args,
+            None => {
+                BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorMonthStartUsage))
+                    .await;
+                return;
+            }
+        };
+
+        if month_starts
+            .announce(args.year, args.month, args.start)
+            .await
+            .is_err()
+        {
+            BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorGeneral)).await;
+            return;
+        }
+
+        // The eve of a month starting later than calculated is still in the month before it
+        let changed_from = args.start.pred_opt().unwrap_or(args.start);
+        if provider.invalidate_from(changed_from).await.is_err() {
+            log::warn!("Previously cached Hijri dates may not follow the new month start");
+        }
+
+        let month = CurrentDateResponse::from_hijri_ymd(
+            &HijriYmd {
+                year: args.year,
+                month: args.month,
+                day: 1,
+            },
+            i18n,
+        );
+        let mut message_args = HashMap::new();
+        message_args.insert("month_name", month.month_name);
+        message_args.insert("year", month.year);
+        message_args.insert("date", args.start.format("%d.%m.%Y.").to_string());
+
+        if args.broadcast {
+            scheduler
+                .announce(
+                    bot,
+                    NotificationCategory::MonthStart,
+                    i18n.t_with_args(
+                        &TranslationKey::MonthStartAnnouncement,
+                        message_args.clone(),
+                    ),
+                )
+                .await;
+        }
+
+        BotCore::send_message(
+            bot,
+            chat_id,
+            i18n.t_with_args(&TranslationKey::MonthStartSaved, message_args),
+        )
+        .await;
+    }
+
+    async fn convert_date(
+        bot: &Bot,
+        chat_id: ChatId,
+        input: &str,
+        converter: &DateConverter,
+        i18n: &I18n,
+    ) {
+        let query = match DateQuery::parse(input) {
+            Some(query) => query,
+            None => {
+                BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorConvertUsage))
+                    .await;
+                return;
+            }
+        };
+
+        let (gregorian_date, response) = match converter.convert(query).await {
+            Ok(converted) => converted,
+            Err(AppErrorKind::DateConversion) => {
+                BotCore::send_message(
+                    bot,
+                    chat_id,
+                    i18n.t(&TranslationKey::ErrorConvertInvalidDate),
+                )
+                .await;
+                return;
+            }
+            Err(e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&e.current_date_translation_key()))
+                    .await;
+                return;
+            }
+        };
+
+        let mut args = HashMap::new();
+
+        args.insert(
+            "gregorian_date",
+            gregorian_date.format("%d.%m.%Y.").to_string(),
+        );
+        args.insert("day", response.day);
+        args.insert("month", response.month);
+        args.insert("year", response.year);
+        args.insert("month_name", response.month_name);
+        args.insert("month_ar", response.month_ar);
+
+        BotCore::send_message(
+            bot,
+            chat_id,
+            i18n.t_with_args(&TranslationKey::ConvertedDate, args),
+        )
+        .await;
+    }
+
+    async fn send_calendar(
+        bot: &Bot,
+        chat_id: ChatId,
+        provider: &dyn HijriDateProvider,
+        converter: &DateConverter,
+        events: &EventCatalog,
+        i18n: &I18n,
+        timezone: chrono_tz::Tz,
+    ) {
+        let current_date = match provider.get_current_hijri_date_in(timezone).await {
+            Ok(current_date) => current_date,
+            Err(e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&e.current_date_translation_key()))
+                    .await;
+                return;
+            }
+        };
+
+        let year = current_date.year.parse().unwrap_or_default();
+        let view = match MonthView::load(converter, events, year, current_date.month_number).await {
+            Ok(view) => view,
+            Err(_e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorGeneral)).await;
+                return;
+            }
+        };
+
+        let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+
+        if let Err(err) = bot
+            .send_message(chat_id, view.render(today, i18n))
+            .parse_mode(ParseMode::Html)
+            .reply_markup(view.keyboard(i18n))
+            .await
+        {
+            log::error!("Failed to send the calendar: {}", err);
+        }
+    }
+
+    /// Describes the next start of the event with its Hijri and Gregorian date and a countdown
+    async fn describe_event(
+        event: &IslamicEvent,
+        start: HijriYmd,
+        today: NaiveDate,
+        converter: &DateConverter,
+        i18n: &I18n,
+    ) -> Result<String, AppErrorKind> {
+        let (date, _) = converter.convert(DateQuery::Hijri(start)).await?;
+
+        let mut args = HashMap::new();
+        args.insert("name", event.name(i18n));
+        args.insert("day", start.day.to_string());
+        args.insert(
+            "month_name",
+            CurrentDateResponse::map_translated_month(start.month, i18n),
+        );
+        args.insert("year", start.year.to_string());
+        args.insert("date", date.format("%d.%m.%Y.").to_string());
+        args.insert(
+            "countdown",
+            event.countdown((date - today).num_days(), i18n),
+        );
+
+        let key = if event.night {
+            TranslationKey::EventsItemNight
+        } else {
+            TranslationKey::EventsItem
+        };
+
+        Ok(i18n.t_with_args(&key, args))
+    }
+
+    /// Lists the Sunnah fasts with the ones the user is reminded of, or turns the reminders on or
+    /// off, e.g. `ponedjeljak`, `sve` or `ukloni ašura`
+    async fn change_sunnah_fasts(
+        bot: &Bot,
+        chat_id: ChatId,
+        args: &str,
+        i18n: &I18n,
+        scheduler: &Scheduler,
+    ) {
+        let args = args.to_lowercase();
+        let (remove, fasts) = match args.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => (false, None),
+            ["ukloni" | "remove", "sve" | "all"] | ["ukloni" | "remove"] => {
+                (true, Some(SunnahFast::ALL.to_vec()))
+            }
+            // A fast which isn't recognized gets the usage message, instead of the list
+            ["ukloni" | "remove", fast] => (true, Some(fast.parse().into_iter().collect())),
+            ["sve" | "all"] => (false, Some(SunnahFast::ALL.to_vec())),
+            [fast] => (false, Some(fast.parse().into_iter().collect())),
+            _ => (false, Some(Vec::new())),
+        };
+
+        let message = match fasts {
+            None => match scheduler.get_sunnah_fast_reminders(chat_id.0).await {
+                Ok(reminders) => {
+                    let fasts: Vec<String> = SunnahFast::ALL
+                        .iter()
+                        .map(|fast| {
+                            format!(
+                                "{} {} (/sunnah {})",
+                                if reminders.contains(fast) {
+                                    "✅"
+                                } else {
+                                    "▫️"
+                                },
+                                i18n.t(&fast.translation_key()),
+                                fast.as_str()
+                            )
+                        })
+                        .collect();
+                    let mut message_args = HashMap::new();
+                    message_args.insert("fasts", fasts.join("\n"));
+
+                    i18n.t_with_args(&TranslationKey::SunnahFasts, message_args)
+                }
+                Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+            },
+            Some(fasts) if fasts.is_empty() => i18n.t(&TranslationKey::ErrorSunnahFastUsage),
+            Some(fasts) if remove => {
+                let mut result = Ok(());
+                for fast in fasts {
+                    result =
+                        result.and(scheduler.remove_sunnah_fast_reminder(chat_id.0, fast).await);
+                }
+
+                match result {
+                    Ok(()) => i18n.t(&TranslationKey::SunnahFastsRemoved),
+                    Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+                }
+            }
+            Some(fasts) => {
+                let mut result = Ok(());
+                for fast in &fasts {
+                    result = result.and(
+                        scheduler
+                            .schedule_sunnah_fast_reminder(bot.clone(), chat_id.0, *fast)
+                            .await,
+                    );
+                }
+
+                match result {
+                    Ok(()) => {
+                        let mut message_args = HashMap::new();
+                        message_args.insert(
+                            "fast",
+                            fasts
+                                .iter()
+                                .map(|fast| i18n.t(&fast.translation_key()))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        );
+
+                        i18n.t_with_args(&TranslationKey::SunnahFastsSaved, message_args)
+                    }
+                    Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+                }
+            }
+        };
+
+        BotCore::send_message(bot, chat_id, message).await;
+    }
+
+    /// Counts a fasted day of Shawwal, or takes one back with `poništi`
+    async fn count_shawwal_fast(
+        bot: &Bot,
+        chat_id: ChatId,
+        args: &str,
+        provider: &dyn HijriDateProvider,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+    ) {
+        let days = match args.trim().to_lowercase().as_str() {
+            "" => 1,
+            "poništi" | "ponisti" | "undo" => -1,
+            _ => {
+                BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorSunnahFastUsage))
+                    .await;
+                return;
+            }
+        };
+
+        let timezone = get_user_timezone(pool, chat_id.0).await;
+        let current_date = match provider.get_current_hijri_date_in(timezone).await {
+            Ok(current_date) => current_date,
+            Err(e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&e.current_date_translation_key()))
+                    .await;
+                return;
+            }
+        };
+
+        if current_date.month_number != 10 {
+            BotCore::send_message(
+                bot,
+                chat_id,
+                i18n.t(&TranslationKey::ErrorShawwalFastsOutsideShawwal),
+            )
+            .await;
+            return;
+        }
+
+        let year = current_date.year.parse().unwrap_or_default();
+        let message = match add_shawwal_fasts(pool, chat_id.0, year, days).await {
+            Ok(SHAWWAL_FASTS) if days > 0 => i18n.t(&TranslationKey::ShawwalFastsCompleted),
+            Ok(count) => {
+                let mut args = HashMap::new();
+                args.insert("count", count.to_string());
+
+                i18n.t_with_args(&TranslationKey::ShawwalFasts, args)
+            }
+            Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+        };
+
+        BotCore::send_message(bot, chat_id, message).await;
+    }
+
+    /// Tells whether fasting today, or tomorrow with `sutra`, is obligatory, recommended,
+    /// disliked or forbidden
+    async fn send_fasting_ruling(
+        bot: &Bot,
+        chat_id: ChatId,
+        args: &str,
+        provider: &dyn HijriDateProvider,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+    ) {
+        let timezone = get_user_timezone(pool, chat_id.0).await;
+        let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+        let date = match args.trim().to_lowercase().as_str() {
+            "" | "danas" | "today" => Some((today, TranslationKey::FastingToday)),
+            "sutra" | "tomorrow" => today
+                .succ_opt()
+                .map(|tomorrow| (tomorrow, TranslationKey::FastingTomorrow)),
+            _ => None,
+        };
+        let Some((date, day_name)) = date else {
+            BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorFastingUsage)).await;
+            return;
+        };
+
+        let hijri_date = match provider.get_hijri_date(date).await {
+            Ok(hijri_date) => hijri_date,
+            Err(e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&e.current_date_translation_key()))
+                    .await;
+                return;
+            }
+        };
+
+        let ruling = FastingRuling::of(date, hijri_date.month_number, hijri_date.day_number);
+
+        let mut args = HashMap::new();
+        args.insert("day_name", i18n.t(&day_name));
+        args.insert("day", hijri_date.day_number.to_string());
+        args.insert(
+            "month_name",
+            CurrentDateResponse::map_translated_month(hijri_date.month_number, i18n),
+        );
+        args.insert("year", hijri_date.year);
+        args.insert("date", date.format("%d.%m.%Y.").to_string());
+        args.insert("status", i18n.t(&ruling.status.translation_key()));
+        args.insert(
+            "reason",
+            ruling
+                .reason
+                .map(|reason| i18n.t(&reason.translation_key()))
+                .unwrap_or_default(),
+        );
+
+        BotCore::send_message(
+            bot,
+            chat_id,
+            i18n.t_with_args(&TranslationKey::Fasting, args)
+                .trim_end()
+                .to_string(),
+        )
+        .await;
+    }
+
+    /// Lists the upcoming events with countdowns, or a single event, e.g. `10` or `ramazan`
+    async fn send_events(
+        bot: &Bot,
+        chat_id: ChatId,
+        args: &str,
+        converter: &DateConverter,
+        events: &EventCatalog,
+        i18n: &I18n,
+        timezone: chrono_tz::Tz,
+    ) {
+        let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+        let hijri_today = match converter.convert(DateQuery::Gregorian(today)).await {
+            Ok((_, response)) => HijriYmd {
+                year: response.year.parse().unwrap_or_default(),
+                month: response.month_number,
+                day: response.day_number,
+            },
+            Err(e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&e.current_date_translation_key()))
+                    .await;
+                return;
+            }
+        };
+
+        let args = args.trim();
+        let upcoming = events.upcoming(&hijri_today);
+
+        let message = if args.is_empty() || args.parse::<usize>().is_ok() {
+            let count = args.parse().unwrap_or(DEFAULT_EVENTS_COUNT).max(1);
+            let mut items = Vec::new();
+
+            for (event, start) in upcoming.into_iter().take(count) {
+                match TelegramBot::describe_event(event, start, today, converter, i18n).await {
+                    Ok(item) => items.push(item),
+                    Err(_e) => log::error!("Failed to convert the date of the event {}", event.id),
+                }
+            }
+
+            let mut message_args = HashMap::new();
+            message_args.insert("events", items.join("\n\n"));
+            i18n.t_with_args(&TranslationKey::Events, message_args)
+        } else {
+            let Some((event, start)) = events
+                .find(args)
+                .and_then(|found| upcoming.into_iter().find(|(event, _)| event.id == found.id))
+            else {
+                let mut message_args = HashMap::new();
+                message_args.insert("query", args.to_string());
+                message_args.insert(
+                    "events",
+                    events
+                        .events()
+                        .iter()
+                        .map(|event| event.id.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                );
+                BotCore::send_message(
+                    bot,
+                    chat_id,
+                    i18n.t_with_args(&TranslationKey::ErrorEventNotFound, message_args),
+                )
+                .await;
+                return;
+            };
+
+            match TelegramBot::describe_event(event, start, today, converter, i18n).await {
+                Ok(item) => match event.text(i18n) {
+                    Some(text) => format!("{}\n\n{}", item, text),
+                    None => item,
+                },
+                Err(e) => i18n.t(&e.current_date_translation_key()),
+            }
+        };
+
+        BotCore::send_message(bot, chat_id, message).await;
+    }
+
+    /// Pages the calendar message to the month of the pressed button
+    async fn page_calendar(
+        bot: &Bot,
+        query: &CallbackQuery,
+        converter: &DateConverter,
+        events: &EventCatalog,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+    ) -> Result<(), RequestError> {
+        let page = query
+            .data
+            .as_deref()
+            .and_then(MonthView::parse_callback_data);
+        let (message, (year, month)) = match (&query.message, page) {
+            (Some(message), Some(page)) => (message, page),
+            _ => {
+                log::warn!("Unexpected callback query: {:?}", query.data);
+                bot.answer_callback_query(query.id.clone()).await?;
+                return Ok(());
+            }
+        };
+
+        let view = match MonthView::load(converter, events, year, month).await {
+            Ok(view) => view,
+            Err(_e) => {
+                bot.answer_callback_query(query.id.clone())
+                    .text(i18n.t(&TranslationKey::ErrorCalendarOutOfRange))
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let timezone = get_user_timezone(pool, message.chat().id.0).await;
+        let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+
+        bot.edit_message_text(message.chat().id, message.id(), view.render(today, i18n))
+            .parse_mode(ParseMode::Html)
+            .reply_markup(view.keyboard(i18n))
+            .await?;
+        bot.answer_callback_query(query.id.clone()).await?;
+
+        Ok(())
+    }
+
+    fn timezone_message(key: &TranslationKey, timezone: chrono_tz::Tz, i18n: &I18n) -> String {
+        let mut args = HashMap::new();
+        args.insert("timezone", timezone.name().to_string());
+        args.insert(
+            "time",
+            chrono::Utc::now()
+                .with_timezone(&timezone)
+                .format("%H:%M")
+                .to_string(),
+        );
+
+        i18n.t_with_args(key, args)
+    }
+
+    async fn change_timezone(
+        bot: &Bot,
+        msg: &Message,
+        query: &str,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+    ) {
+        let chat_id = msg.chat.id;
+
+        if query.trim().is_empty() {
+            let timezone = get_user_timezone(pool, chat_id.0).await;
+            BotCore::send_message(
+                bot,
+                chat_id,
+                TelegramBot::timezone_message(&TranslationKey::TimezoneCurrent, timezone, i18n),
+            )
+            .await;
+            return;
+        }
+
+        let timezones = search_timezones(query);
+
+        match timezones.as_slice() {
+            [] => {
+                let mut args = HashMap::new();
+                args.insert("query", query.trim().to_string());
+                BotCore::send_message(
+                    bot,
+                    chat_id,
+                    i18n.t_with_args(&TranslationKey::ErrorTimezoneNotFound, args),
+                )
+                .await;
+            }
+            [timezone] => {
+                let username = msg.from.as_ref().and_then(|user| user.username.clone());
+                let message = match set_user_timezone(pool, chat_id.0, username, *timezone).await {
+                    Ok(()) => TelegramBot::timezone_message(
+                        &TranslationKey::TimezoneSaved,
+                        *timezone,
+                        i18n,
+                    ),
+                    Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+                };
+                BotCore::send_message(bot, chat_id, message).await;
+            }
+            timezones if timezones.len() <= MAX_TIMEZONE_CHOICES => {
+                let keyboard = InlineKeyboardMarkup::new(timezones.iter().map(|timezone| {
+                    [InlineKeyboardButton::callback(
+                        timezone.name(),
+                        format!("{}{}", TIMEZONE_CALLBACK_PREFIX, timezone.name()),
+                    )]
+                }));
+
+                if let Err(err) = bot
+                    .send_message(chat_id, i18n.t(&TranslationKey::TimezoneChoose))
+                    .reply_markup(keyboard)
+                    .await
+                {
+                    log::error!("Failed to send the timezone choices: {}", err);
+                }
+            }
+            timezones => {
+                let mut args = HashMap::new();
+                args.insert("count", timezones.len().to_string());
+                BotCore::send_message(
+                    bot,
+                    chat_id,
+                    i18n.t_with_args(&TranslationKey::ErrorTimezoneTooMany, args),
+                )
+                .await;
+            }
         }
     }
 
+    async fn change_delivery_time(
+        bot: &Bot,
+        msg: &Message,
+        args: &str,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+    ) {
+        let chat_id = msg.chat.id;
+        let args = args.trim();
+        let timezone = get_user_timezone(pool, chat_id.0).await;
+
+        let mut message_args = HashMap::new();
+        message_args.insert("timezone", timezone.name().to_string());
+
+        if args.is_empty() {
+            let text = match get_user_delivery_time(pool, chat_id.0).await {
+                Some(delivery_time) => {
+                    message_args.insert("time", delivery_time.format("%H:%M").to_string());
+                    i18n.t_with_args(&TranslationKey::DeliveryTimeCurrent, message_args)
+                }
+                None => i18n.t_with_args(&TranslationKey::DeliveryTimeEvening, message_args),
+            };
+            BotCore::send_message(bot, chat_id, text).await;
+            return;
+        }
+
+        let delivery_time = if ["akšam", "aksam", "evening", "ukloni", "remove"]
+            .contains(&args.to_lowercase().as_str())
+        {
+            None
+        } else {
+            match parse_delivery_time(args) {
+                Some(delivery_time) => Some(delivery_time),
+                None => {
+                    message_args.insert("query", args.to_string());
+                    BotCore::send_message(
+                        bot,
+                        chat_id,
+                        i18n.t_with_args(&TranslationKey::ErrorDeliveryTimeInvalid, message_args),
+                    )
+                    .await;
+                    return;
+                }
+            }
+        };
+
+        let username = msg.from.as_ref().and_then(|user| user.username.clone());
+        let text = match set_user_delivery_time(pool, chat_id.0, username, delivery_time).await {
+            Ok(()) => match delivery_time {
+                Some(delivery_time) => {
+                    message_args.insert("time", delivery_time.format("%H:%M").to_string());
+                    i18n.t_with_args(&TranslationKey::DeliveryTimeSaved, message_args)
+                }
+                None => i18n.t(&TranslationKey::DeliveryTimeReset),
+            },
+            Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+        };
+        BotCore::send_message(bot, chat_id, text).await;
+    }
+
+    /// Deletes the jobs and the settings of the user and marks the user inactive, so no
+    /// notifications are sent until the user sends /start again
+    async fn stop(bot: &Bot, chat_id: ChatId, i18n: &I18n, scheduler: &Scheduler) {
+        let text = match scheduler.unsubscribe_user(chat_id.0).await {
+            Ok(()) => i18n.t(&TranslationKey::Stopped),
+            Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+        };
+        BotCore::send_message(bot, chat_id, text).await;
+    }
+
+    /// Buttons turning each category of the notifications on or off
+    fn settings_keyboard(disabled: &[NotificationCategory], i18n: &I18n) -> InlineKeyboardMarkup {
+        InlineKeyboardMarkup::new(NotificationCategory::ALL.iter().map(|category| {
+            [InlineKeyboardButton::callback(
+                format!(
+                    "{} {}",
+                    if disabled.contains(category) {
+                        "🔕"
+                    } else {
+                        "🔔"
+                    },
+                    i18n.t(&category.translation_key())
+                ),
+                format!("{}{}", SETTINGS_CALLBACK_PREFIX, category.as_str()),
+            )]
+        }))
+    }
+
+    async fn send_settings(bot: &Bot, chat_id: ChatId, i18n: &I18n, pool: &Pool<Postgres>) {
+        let disabled = match get_disabled_notifications(pool, chat_id.0).await {
+            Ok(disabled) => disabled,
+            Err(_e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorGeneral)).await;
+                return;
+            }
+        };
+
+        if let Err(err) = bot
+            .send_message(chat_id, i18n.t(&TranslationKey::Settings))
+            .reply_markup(TelegramBot::settings_keyboard(&disabled, i18n))
+            .await
+        {
+            log::error!("Failed to send the settings: {}", err);
+        }
+    }
+
+    /// Turns the category of the pressed button on or off
+    async fn toggle_setting(
+        bot: &Bot,
+        query: &CallbackQuery,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+    ) -> Result<(), RequestError> {
+        let category = query
+            .data
+            .as_deref()
+            .and_then(|data| data.strip_prefix(SETTINGS_CALLBACK_PREFIX))
+            .and_then(|category| category.parse::<NotificationCategory>().ok());
+        let (message, category) = match (&query.message, category) {
+            (Some(message), Some(category)) => (message, category),
+            _ => {
+                log::warn!("Unexpected callback query: {:?}", query.data);
+                bot.answer_callback_query(query.id.clone()).await?;
+                return Ok(());
+            }
+        };
+        let chat_id = message.chat().id;
+
+        let disabled = match get_disabled_notifications(pool, chat_id.0).await {
+            Ok(disabled) => disabled,
+            Err(_e) => {
+                bot.answer_callback_query(query.id.clone())
+                    .text(i18n.t(&TranslationKey::ErrorGeneral))
+                    .await?;
+                return Ok(());
+            }
+        };
+        let enabled = disabled.contains(&category);
+
+        if set_notification_enabled(pool, chat_id.0, category, enabled)
+            .await
+            .is_err()
+        {
+            bot.answer_callback_query(query.id.clone())
+                .text(i18n.t(&TranslationKey::ErrorGeneral))
+                .await?;
+            return Ok(());
+        }
+
+        let disabled: Vec<NotificationCategory> = NotificationCategory::ALL
+            .into_iter()
+            .filter(|other| {
+                if *other == category {
+                    !enabled
+                } else {
+                    disabled.contains(other)
+                }
+            })
+            .collect();
+
+        bot.edit_message_reply_markup(chat_id, message.id())
+            .reply_markup(TelegramBot::settings_keyboard(&disabled, i18n))
+            .await?;
+        bot.answer_callback_query(query.id.clone()).await?;
+
+        Ok(())
+    }
+
+    /// Saves the timezone of the pressed button
+    async fn choose_timezone(
+        bot: &Bot,
+        query: &CallbackQuery,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+    ) -> Result<(), RequestError> {
+        let timezone = query
+            .data
+            .as_deref()
+            .and_then(|data| data.strip_prefix(TIMEZONE_CALLBACK_PREFIX))
+            .and_then(|name| name.parse::<chrono_tz::Tz>().ok());
+        let (message, timezone) = match (&query.message, timezone) {
+            (Some(message), Some(timezone)) => (message, timezone),
+            _ => {
+                log::warn!("Unexpected callback query: {:?}", query.data);
+                bot.answer_callback_query(query.id.clone()).await?;
+                return Ok(());
+            }
+        };
+
+        let text = match set_user_timezone(
+            pool,
+            message.chat().id.0,
+            query.from.username.clone(),
+            timezone,
+        )
+        .await
+        {
+            Ok(()) => TelegramBot::timezone_message(&TranslationKey::TimezoneSaved, timezone, i18n),
+            Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+        };
+
+        bot.edit_message_text(message.chat().id, message.id(), text)
+            .await?;
+        bot.answer_callback_query(query.id.clone()).await?;
+
+        Ok(())
+    }
+
+    fn current_date_message(day: &IslamicDay, i18n: &I18n) -> String {
+        let date = day.date();
+        let mut args = HashMap::new();
+
+        args.insert("day", date.day.clone());
+        args.insert("month", date.month.clone());
+        args.insert("year", date.year.clone());
+        args.insert("month_name", date.month_name.clone());
+        args.insert("month_ar", date.month_ar.clone());
+
+        let (sunset, evening) = match &day.evening {
+            Some(evening) => evening,
+            None => return i18n.t_with_args(&TranslationKey::CurrentHijriDate, args),
+        };
+        args.insert("sunset", sunset.format("%H:%M").to_string());
+
+        if day.is_after_sunset() {
+            return i18n.t_with_args(&TranslationKey::CurrentHijriDateAfterMaghrib, args);
+        }
+
+        let mut evening_args = HashMap::new();
+        evening_args.insert("sunset", sunset.format("%H:%M").to_string());
+        evening_args.insert("day", evening.day_number.to_string());
+        evening_args.insert("month_name", evening.month_name.clone());
+
+        format!(
+            "{}\n\n{}",
+            i18n.t_with_args(&TranslationKey::CurrentHijriDate, args),
+            i18n.t_with_args(&TranslationKey::NextHijriDateAtMaghrib, evening_args)
+        )
+    }
+
+    async fn change_location(
+        bot: &Bot,
+        msg: &Message,
+        args: &str,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+        scheduler: &Scheduler,
+    ) {
+        let chat_id = msg.chat.id;
+        let args = args.trim();
+
+        if args.is_empty() {
+            let keyboard = KeyboardMarkup::new([[KeyboardButton::new(
+                i18n.t(&TranslationKey::LocationButton),
+            )
+            .request(ButtonRequest::Location)]])
+            .one_time_keyboard()
+            .resize_keyboard();
+
+            if let Err(err) = bot
+                .send_message(chat_id, i18n.t(&TranslationKey::LocationPrompt))
+                .reply_markup(keyboard)
+                .await
+            {
+                log::error!("Failed to send the location prompt: {}", err);
+            }
+            return;
+        }
+
+        if ["ukloni", "remove"].contains(&args.to_lowercase().as_str()) {
+            let username = msg.from.as_ref().and_then(|user| user.username.clone());
+            let text = match set_user_location(pool, chat_id.0, username, None).await {
+                Ok(()) => i18n.t(&TranslationKey::LocationRemoved),
+                Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+            };
+
+            if let Err(err) = bot
+                .send_message(chat_id, text)
+                .reply_markup(KeyboardRemove::new())
+                .await
+            {
+                log::error!("Failed to send the location removal: {}", err);
+            }
+
+            TelegramBot::reschedule_prayer_reminders(bot, chat_id, scheduler).await;
+            return;
+        }
+
+        match Location::parse(args) {
+            Some(location) => {
+                TelegramBot::save_location(bot, msg, location, i18n, pool, scheduler).await
+            }
+            None => {
+                BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorLocationInvalid))
+                    .await;
+            }
+        }
+    }
+
+    /// Saves the location and replies with today's sunset at it
+    async fn save_location(
+        bot: &Bot,
+        msg: &Message,
+        location: Location,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+        scheduler: &Scheduler,
+    ) {
+        let chat_id = msg.chat.id;
+        let username = msg.from.as_ref().and_then(|user| user.username.clone());
+
+        let text = match set_user_location(pool, chat_id.0, username, Some(location)).await {
+            Ok(()) => {
+                let timezone = get_user_timezone(pool, chat_id.0).await;
+                let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+                let mut args = HashMap::new();
+                args.insert(
+                    "sunset",
+                    solar::sunset(today, location)
+                        .map(|sunset| sunset.with_timezone(&timezone).format("%H:%M").to_string())
+                        .unwrap_or("–".to_string()),
+                );
+
+                i18n.t_with_args(&TranslationKey::LocationSaved, args)
+            }
+            Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+        };
+
+        if let Err(err) = bot
+            .send_message(chat_id, text)
+            .reply_markup(KeyboardRemove::new())
+            .await
+        {
+            log::error!("Failed to send the saved location: {}", err);
+        }
+
+        TelegramBot::reschedule_prayer_reminders(bot, chat_id, scheduler).await;
+    }
+
+    /// Moves the pending prayer reminder of the user after a change of the prayer times
+    async fn reschedule_prayer_reminders(bot: &Bot, chat_id: ChatId, scheduler: &Scheduler) {
+        if scheduler
+            .schedule_prayer_reminders(bot.clone(), chat_id.0)
+            .await
+            .is_err()
+        {
+            log::error!("Failed to reschedule the prayer reminders of {}", chat_id);
+        }
+    }
+
+    /// Saves the calculation method or the Asr method given as the arguments. Returns `false`
+    /// when the arguments aren't valid.
+    async fn change_prayer_times_settings(
+        bot: &Bot,
+        msg: &Message,
+        args: &str,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+        scheduler: &Scheduler,
+        default_config: PrayerTimesConfig,
+    ) -> bool {
+        let chat_id = msg.chat.id;
+        let mut config = get_user_prayer_times_config(pool, chat_id.0, default_config).await;
+
+        for arg in args.split_whitespace() {
+            if let Ok(method) = arg.parse::<CalculationMethod>() {
+                config.method = method;
+            } else if let Ok(asr) = arg.parse::<AsrMethod>() {
+                config.asr = asr;
+            } else {
+                BotCore::send_message(bot, chat_id, i18n.t(&TranslationKey::ErrorPrayerTimesUsage))
+                    .await;
+                return false;
+            }
+        }
+
+        let username = msg.from.as_ref().and_then(|user| user.username.clone());
+        let message = match set_user_prayer_times_config(pool, chat_id.0, username, config).await {
+            Ok(()) => i18n.t(&TranslationKey::PrayerTimesSettingsSaved),
+            Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+        };
+        BotCore::send_message(bot, chat_id, message).await;
+
+        TelegramBot::reschedule_prayer_reminders(bot, chat_id, scheduler).await;
+
+        true
+    }
+
+    fn prayer_reminder_description(reminder: &PrayerReminder, i18n: &I18n) -> String {
+        let mut args = HashMap::new();
+        args.insert("prayer", i18n.t(&reminder.prayer.translation_key()));
+        args.insert("minutes", reminder.minutes_before.to_string());
+
+        if reminder.minutes_before == 0 {
+            i18n.t_with_args(&TranslationKey::PrayerReminderItemAt, args)
+        } else {
+            i18n.t_with_args(&TranslationKey::PrayerReminderItemBefore, args)
+        }
+    }
+
+    /// Lists the prayer reminders of the user, or adds or removes one, e.g. `akšam 10` or
+    /// `ukloni akšam`
+    async fn change_prayer_reminders(
+        bot: &Bot,
+        msg: &Message,
+        args: &str,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+        scheduler: &Scheduler,
+    ) {
+        let chat_id = msg.chat.id;
+        let parts: Vec<String> = args
+            .split_whitespace()
+            .map(|part| part.to_lowercase())
+            .collect();
+
+        let message = match parts
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [] => match get_prayer_reminders(pool, chat_id.0).await {
+                Ok(reminders) if reminders.is_empty() => {
+                    i18n.t(&TranslationKey::PrayerRemindersEmpty)
+                }
+                Ok(reminders) => {
+                    let reminders: Vec<String> = reminders
+                        .iter()
+                        .map(|reminder| {
+                            format!(
+                                "- {}",
+                                TelegramBot::prayer_reminder_description(reminder, i18n)
+                            )
+                        })
+                        .collect();
+                    let mut args = HashMap::new();
+                    args.insert("reminders", reminders.join("\n"));
+
+                    i18n.t_with_args(&TranslationKey::PrayerReminders, args)
+                }
+                Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+            },
+            ["ukloni" | "remove", prayer @ ..] => {
+                let prayer = match prayer {
+                    [] | ["sve" | "all"] => None,
+                    [prayer] => match prayer.parse() {
+                        Ok(prayer) => Some(prayer),
+                        Err(_e) => {
+                            BotCore::send_message(
+                                bot,
+                                chat_id,
+                                i18n.t(&TranslationKey::ErrorPrayerReminderUsage),
+                            )
+                            .await;
+                            return;
+                        }
+                    },
+                    _ => {
+                        BotCore::send_message(
+                            bot,
+                            chat_id,
+                            i18n.t(&TranslationKey::ErrorPrayerReminderUsage),
+                        )
+                        .await;
+                        return;
+                    }
+                };
+
+                match remove_prayer_reminders(pool, chat_id.0, prayer).await {
+                    Ok(()) => i18n.t(&TranslationKey::PrayerRemindersRemoved),
+                    Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+                }
+            }
+            [prayer, minutes @ ..] => {
+                let minutes_before = match minutes {
+                    [] => Some(0),
+                    [minutes] => minutes.trim_end_matches("min").parse().ok(),
+                    _ => None,
+                };
+                let reminder = match (prayer.parse(), minutes_before) {
+                    (Ok(prayer), Some(minutes_before)) => {
+                        PrayerReminder::new(prayer, minutes_before)
+                    }
+                    _ => None,
+                };
+                let reminder = match reminder {
+                    Some(reminder) => reminder,
+                    None => {
+                        BotCore::send_message(
+                            bot,
+                            chat_id,
+                            i18n.t(&TranslationKey::ErrorPrayerReminderUsage),
+                        )
+                        .await;
+                        return;
+                    }
+                };
+
+                if get_user_location(pool, chat_id.0).await.is_none() {
+                    BotCore::send_message(
+                        bot,
+                        chat_id,
+                        i18n.t(&TranslationKey::ErrorPrayerTimesNoLocation),
+                    )
+                    .await;
+                    return;
+                }
+
+                match set_prayer_reminder(pool, chat_id.0, reminder).await {
+                    Ok(()) => {
+                        let mut args = HashMap::new();
+                        args.insert(
+                            "reminder",
+                            TelegramBot::prayer_reminder_description(&reminder, i18n),
+                        );
+
+                        i18n.t_with_args(&TranslationKey::PrayerReminderSaved, args)
+                    }
+                    Err(_e) => i18n.t(&TranslationKey::ErrorGeneral),
+                }
+            }
+        };
+
+        BotCore::send_message(bot, chat_id, message).await;
+
+        if !parts.is_empty() {
+            TelegramBot::reschedule_prayer_reminders(bot, chat_id, scheduler).await;
+        }
+    }
+
+    async fn send_prayer_times(
+        bot: &Bot,
+        chat_id: ChatId,
+        provider: &dyn HijriDateProvider,
+        i18n: &I18n,
+        pool: &Pool<Postgres>,
+        default_config: PrayerTimesConfig,
+    ) {
+        let config = get_user_prayer_times_config(pool, chat_id.0, default_config).await;
+
+        let location = match get_user_location(pool, chat_id.0).await {
+            Some(location) => location,
+            None => {
+                BotCore::send_message(
+                    bot,
+                    chat_id,
+                    i18n.t(&TranslationKey::ErrorPrayerTimesNoLocation),
+                )
+                .await;
+                return;
+            }
+        };
+
+        let timezone = get_user_timezone(pool, chat_id.0).await;
+        let today = chrono::Utc::now().with_timezone(&timezone).date_naive();
+
+        let prayer_times = match PrayerTimes::calculate(today, location, &config) {
+            Some(prayer_times) => prayer_times,
+            None => {
+                BotCore::send_message(
+                    bot,
+                    chat_id,
+                    i18n.t(&TranslationKey::ErrorPrayerTimesUnavailable),
+                )
+                .await;
+                return;
+            }
+        };
+
+        let hijri_date = match provider.get_current_hijri_date_in(timezone).await {
+            Ok(hijri_date) => hijri_date,
+            Err(e) => {
+                BotCore::send_message(bot, chat_id, i18n.t(&e.current_date_translation_key()))
+                    .await;
+                return;
+            }
+        };
+
+        let times: Vec<String> = Prayer::ALL
+            .iter()
+            .map(|prayer| {
+                format!(
+                    "{}: {}",
+                    i18n.t(&prayer.translation_key()),
+                    prayer_times
+                        .time(*prayer)
+                        .with_timezone(&timezone)
+                        .format("%H:%M")
+                )
+            })
+            .collect();
+
+        let mut message_args = HashMap::new();
+        message_args.insert("date", today.format("%d.%m.%Y.").to_string());
+        message_args.insert(
+            "hijri_date",
+            format!(
+                "{}. {} {}.",
+                hijri_date.day_number, hijri_date.month_name, hijri_date.year
+            ),
+        );
+        message_args.insert("times", times.join("\n"));
+        message_args.insert("method", config.method.display_name().to_string());
+        message_args.insert("asr_method", i18n.t(&config.asr.translation_key()));
+
+        BotCore::send_message(
+            bot,
+            chat_id,
+            i18n.t_with_args(&TranslationKey::PrayerTimes, message_args),
+        )
+        .await;
+    }
+
     pub async fn run(&self) {
         log::info!("Starting Hijri bot...");
 
         let i18n = Arc::clone(&self.i18n);
-        let api = Arc::clone(&self.api);
+        let provider = Arc::clone(&self.provider);
+        let converter = Arc::clone(&self.converter);
+        let events = Arc::clone(&self.events);
         let bot = self.bot.clone();
         let pool = self.pool.clone();
         let scheduler = Arc::clone(&self.scheduler);
+        let month_starts = Arc::clone(&self.month_starts);
+        let prayer_times_config = self.prayer_times_config;
+        let admin_chat_ids = Arc::clone(&self.admin_chat_ids);
 
-        Command::repl(bot, {
-            move |bot: Bot, msg: Message, cmd: Command| {
+        let command_handler = Update::filter_message()
+            .filter_command::<Command>()
+            .endpoint({
                 let i18n = Arc::clone(&i18n);
-                let api = Arc::clone(&api);
+                let provider = Arc::clone(&provider);
+                let converter = Arc::clone(&converter);
+                let events = Arc::clone(&events);
                 let pool = Arc::clone(&pool);
                 let scheduler = Arc::clone(&scheduler);
 
-                async move {
-                    log::debug!("Received command: {:?}", cmd);
+                move |bot: Bot, msg: Message, cmd: Command| {
+                    let i18n = Arc::clone(&i18n);
+                    let provider = Arc::clone(&provider);
+                    let converter = Arc::clone(&converter);
+                    let events = Arc::clone(&events);
+                    let pool = Arc::clone(&pool);
+                    let scheduler = Arc::clone(&scheduler);
+                    let month_starts = Arc::clone(&month_starts);
+                    let admin_chat_ids = Arc::clone(&admin_chat_ids);
+
+                    async move {
+                        log::debug!("Received command: {:?}", cmd);
 
-                    match cmd {
-                        Command::Help => {
-                            BotCore::send_message(&bot, msg.chat.id, i18n.t(&TranslationKey::Help))
+                        match cmd {
+                            Command::Help => {
+                                BotCore::send_message(
+                                    &bot,
+                                    msg.chat.id,
+                                    i18n.t(&TranslationKey::Help),
+                                )
                                 .await;
-                        }
-                        Command::Date => {
-                            let res = api.get_current_hijri_date().await;
+                            }
+                            Command::Date => {
+                                let timezone = get_user_timezone(&pool, msg.chat.id.0).await;
+                                let location = get_user_location(&pool, msg.chat.id.0).await;
+                                let res = IslamicDay::current(&*provider, timezone, location).await;
 
-                            match res {
-                                Err(_e) => {
-                                    BotCore::send_message(
+                                let message = match res {
+                                    Err(e) => i18n.t(&e.current_date_translation_key()),
+                                    Ok(day) => TelegramBot::current_date_message(&day, &i18n),
+                                };
+
+                                BotCore::send_message(&bot, msg.chat.id, message).await;
+                            }
+                            Command::Convert(input) => {
+                                TelegramBot::convert_date(
+                                    &bot,
+                                    msg.chat.id,
+                                    &input,
+                                    &converter,
+                                    &i18n,
+                                )
+                                .await;
+                            }
+                            Command::Calendar => {
+                                let timezone = get_user_timezone(&pool, msg.chat.id.0).await;
+                                TelegramBot::send_calendar(
+                                    &bot,
+                                    msg.chat.id,
+                                    &*provider,
+                                    &converter,
+                                    &events,
+                                    &i18n,
+                                    timezone,
+                                )
+                                .await;
+                            }
+                            Command::Events(args) => {
+                                let timezone = get_user_timezone(&pool, msg.chat.id.0).await;
+                                TelegramBot::send_events(
+                                    &bot,
+                                    msg.chat.id,
+                                    &args,
+                                    &converter,
+                                    &events,
+                                    &i18n,
+                                    timezone,
+                                )
+                                .await;
+                            }
+                            Command::Timezone(query) => {
+                                TelegramBot::change_timezone(&bot, &msg, &query, &i18n, &pool)
+                                    .await;
+                            }
+                            Command::Location(args) => {
+                                TelegramBot::change_location(
+                                    &bot, &msg, &args, &i18n, &pool, &scheduler,
+                                )
+                                .await;
+                            }
+                            Command::PrayerTimes(args) => {
+                                if !args.trim().is_empty()
+                                    && !TelegramBot::change_prayer_times_settings(
                                         &bot,
-                                        msg.chat.id,
-                                        i18n.t(&TranslationKey::ErrorCurrentDate),
+                                        &msg,
+                                        &args,
+                                        &i18n,
+                                        &pool,
+                                        &scheduler,
+                                        prayer_times_config,
                                     )
-                                    .await;
+                                    .await
+                                {
+                                    return Ok(());
                                 }
-                                Ok(response) => {
-                                    let mut args = HashMap::new();
-
-                                    args.insert("day", response.day);
-                                    args.insert("month", response.month);
-                                    args.insert("year", response.year);
-                                    args.insert("month_name", response.month_name);
-                                    args.insert("month_ar", response.month_ar);
 
-                                    BotCore::send_message(
-                                        &bot,
-                                        msg.chat.id,
-                                        i18n.t_with_args(&TranslationKey::CurrentHijriDate, args),
-                                    )
+                                TelegramBot::send_prayer_times(
+                                    &bot,
+                                    msg.chat.id,
+                                    &*provider,
+                                    &i18n,
+                                    &pool,
+                                    prayer_times_config,
+                                )
+                                .await;
+                            }
+                            Command::Reminder(args) => {
+                                TelegramBot::change_prayer_reminders(
+                                    &bot, &msg, &args, &i18n, &pool, &scheduler,
+                                )
+                                .await;
+                            }
+                            Command::Sunnah(args) => {
+                                TelegramBot::change_sunnah_fasts(
+                                    &bot,
+                                    msg.chat.id,
+                                    &args,
+                                    &i18n,
+                                    &scheduler,
+                                )
+                                .await;
+                            }
+                            Command::Shawwal(args) => {
+                                TelegramBot::count_shawwal_fast(
+                                    &bot,
+                                    msg.chat.id,
+                                    &args,
+                                    &*provider,
+                                    &i18n,
+                                    &pool,
+                                )
+                                .await;
+                            }
+                            Command::Fasting(args) => {
+                                TelegramBot::send_fasting_ruling(
+                                    &bot,
+                                    msg.chat.id,
+                                    &args,
+                                    &*provider,
+                                    &i18n,
+                                    &pool,
+                                )
+                                .await;
+                            }
+                            Command::Time(args) => {
+                                TelegramBot::change_delivery_time(&bot, &msg, &args, &i18n, &pool)
                                     .await;
-                                }
                             }
-                        }
-                        Command::Start => {
-                            log::debug!("User started the bot: {:?}", msg.chat.id);
-                            let user_id = Uuid::new_v4();
-                            sqlx::query!(
-                                "
+                            Command::Settings => {
+                                TelegramBot::send_settings(&bot, msg.chat.id, &i18n, &pool).await;
+                            }
+                            Command::Start => {
+                                log::debug!("User started the bot: {:?}", msg.chat.id);
+                                let user_id = Uuid::new_v4();
+                                sqlx::query!(
+                                    "
                                     INSERT INTO users (id, chat_id, username) 
                                     VALUES ($1, $2, $3)
                                     ON CONFLICT (chat_id)
-                                    DO UPDATE SET chat_id = EXCLUDED.chat_id
+                                    DO UPDATE SET active = TRUE
                                     RETURNING id
                                 ",
-                                user_id,
-                                msg.chat.id.0 as i64,
-                                msg.from.and_then(|m| m.username.clone())
-                            )
-                            .fetch_one(&*pool)
-                            .await
-                            .map_err(|e| {
-                                log::error!("Failed to insert user: {}", e);
-                                RequestError::Api(ApiError::CantInitiateConversation)
-                            })?;
-                            scheduler
-                                .schedule_white_days_message(bot.clone(), msg.chat.id.0)
+                                    user_id,
+                                    msg.chat.id.0 as i64,
+                                    msg.from.and_then(|m| m.username.clone())
+                                )
+                                .fetch_one(&*pool)
                                 .await
-                                .map_err(|_e| {
+                                .map_err(|e| {
+                                    log::error!("Failed to insert user: {}", e);
                                     RequestError::Api(ApiError::CantInitiateConversation)
                                 })?;
-                            BotCore::send_message(
-                                &bot,
-                                msg.chat.id,
-                                i18n.t(&TranslationKey::WelcomeMessage),
-                            )
-                            .await;
+                                BotCore::send_message(
+                                    &bot,
+                                    msg.chat.id,
+                                    i18n.t(&TranslationKey::WelcomeMessage),
+                                )
+                                .await;
+                            }
+                            Command::Stop => {
+                                TelegramBot::stop(&bot, msg.chat.id, &i18n, &scheduler).await;
+                            }
+                            Command::MonthStart(args) => {
+                                if !admin_chat_ids.contains(&msg.chat.id.0) {
+                                    BotCore::send_message(
+                                        &bot,
+                                        msg.chat.id,
+                                        i18n.t(&TranslationKey::ErrorNotAuthorized),
+                                    )
+                                    .await;
+                                    return Ok(());
+                                }
+
+                                TelegramBot::announce_month_start(
+                                    &bot,
+                                    msg.chat.id,
+                                    &args,
+                                    &*provider,
+                                    &month_starts,
+                                    &i18n,
+                                    &scheduler,
+                                )
+                                .await;
+                            }
                         }
+
+                        Ok::<(), RequestError>(())
                     }
+                }
+            });
+
+        let location_handler = Update::filter_message()
+            .filter_map(|msg: Message| msg.location().copied())
+            .endpoint({
+                let i18n = Arc::clone(&i18n);
+                let pool = Arc::clone(&pool);
+                let scheduler = Arc::clone(&scheduler);
+
+                move |bot: Bot, msg: Message, location: teloxide::types::Location| {
+                    let i18n = Arc::clone(&i18n);
+                    let pool = Arc::clone(&pool);
+                    let scheduler = Arc::clone(&scheduler);
 
-                    Ok(())
+                    async move {
+                        match Location::new(location.latitude, location.longitude) {
+                            Some(location) => {
+                                TelegramBot::save_location(
+                                    &bot, &msg, location, &i18n, &pool, &scheduler,
+                                )
+                                .await;
+                            }
+                            None => log::warn!("Invalid location received: {:?}", location),
+                        }
+
+                        Ok::<(), RequestError>(())
+                    }
                 }
-            }
-        })
+            });
+
+        let callback_handler =
+            Update::filter_callback_query().endpoint(move |bot: Bot, query: CallbackQuery| {
+                let i18n = Arc::clone(&i18n);
+                let converter = Arc::clone(&converter);
+                let events = Arc::clone(&events);
+                let pool = Arc::clone(&pool);
+
+                async move {
+                    match query.data.as_deref() {
+                        Some(data) if data.starts_with(TIMEZONE_CALLBACK_PREFIX) => {
+                            TelegramBot::choose_timezone(&bot, &query, &i18n, &pool).await
+                        }
+                        Some(data) if data.starts_with(SETTINGS_CALLBACK_PREFIX) => {
+                            TelegramBot::toggle_setting(&bot, &query, &i18n, &pool).await
+                        }
+                        _ => {
+                            TelegramBot::page_calendar(
+                                &bot, &query, &converter, &events, &i18n, &pool,
+                            )
+                            .await
+                        }
+                    }
+                }
+            });
+
+        Dispatcher::builder(
+            bot,
+            dptree::entry()
+                .branch(command_handler)
+                .branch(location_handler)
+                .branch(callback_handler),
+        )
+        .enable_ctrlc_handler()
+        .build()
+        .dispatch()
         .await;
     }
 }
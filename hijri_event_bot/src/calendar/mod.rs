@@ -0,0 +1,290 @@
+use std::{str::FromStr, sync::Arc};
+
+use chrono::NaiveDate;
+
+use crate::{
+    api::{CurrentDateResponse, HijriApi},
+    error::AppErrorKind,
+    i18n::instance::I18n,
+    provider::{HijriDateProvider, ProviderFuture},
+};
+
+mod tabular;
+mod umm_al_qura;
+mod umm_al_qura_data;
+
+pub const DEFAULT_TIMEZONE: chrono_tz::Tz = chrono_tz::Tz::Europe__Sarajevo;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalendarMethod {
+    /// The arithmetic (tabular) Islamic calendar with the civil epoch
+    Tabular,
+    /// The Umm al-Qura calendar of Saudi Arabia, falling back to the tabular calendar
+    /// outside of the embedded table
+    UmmAlQura,
+}
+
+impl CalendarMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarMethod::Tabular => "tabular",
+            CalendarMethod::UmmAlQura => "umm_al_qura",
+        }
+    }
+}
+
+impl FromStr for CalendarMethod {
+    type Err = AppErrorKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "tabular" => Ok(CalendarMethod::Tabular),
+            "umm_al_qura" | "ummalqura" => Ok(CalendarMethod::UmmAlQura),
+            _ => {
+                log::error!("Unknown calendar method: {}", value);
+                Err(AppErrorKind::DateConversion)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HijriYmd {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl std::fmt::Display for HijriYmd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub fn arabic_month_name(month: u8) -> &'static str {
+    match month {
+        1 => "مُحَرَّم",
+        2 => "صَفَر",
+        3 => "رَبيع الأوَّل",
+        4 => "رَبيع الثاني",
+        5 => "جُمادى الأولى",
+        6 => "جُمادى الآخرة",
+        7 => "رَجَب",
+        8 => "شَعْبان",
+        9 => "رَمَضان",
+        10 => "شَوّال",
+        11 => "ذوالقعدة",
+        12 => "ذوالحجة",
+        _ => "",
+    }
+}
+
+/// Month names as they are commonly written in Bosnian and in English transliterations,
+/// normalized with [`normalize_month_name`]
+const MONTH_NAME_ALIASES: [(u8, &[&str]); 12] = [
+    (1, &["muharrem", "muharram"]),
+    (2, &["safer", "safar"]),
+    (
+        3,
+        &["rebiulevvel", "rabialawwal", "rabiulawwal", "rabiawwal"],
+    ),
+    (
+        4,
+        &[
+            "rebiulahir",
+            "rabialthani",
+            "rabiulakhir",
+            "rabialakhir",
+            "rabiathani",
+        ],
+    ),
+    (
+        5,
+        &["dzumadelula", "jumadaalawwal", "jumadaalula", "jumadaula"],
+    ),
+    (
+        6,
+        &[
+            "dzumadeluhra",
+            "jumadaalthani",
+            "jumadaalakhirah",
+            "jumadaalukhra",
+            "jumadaakhira",
+        ],
+    ),
+    (7, &["redzeb", "radzab", "rajab"]),
+    (8, &["saban", "shaban", "shaaban"]),
+    (9, &["ramazan", "ramadan", "ramadhan"]),
+    (10, &["sevval", "shawwal", "sawwal"]),
+    (
+        11,
+        &[
+            "zulkade",
+            "zulkada",
+            "zilkade",
+            "dhualqidah",
+            "dhulqidah",
+            "dhulqadah",
+        ],
+    ),
+    (
+        12,
+        &[
+            "zulhidzdze",
+            "zulhidze",
+            "zilhidze",
+            "dhualhijjah",
+            "dhulhijjah",
+            "zulhijjah",
+        ],
+    ),
+];
+
+pub fn normalize_month_name(value: &str) -> String {
+    value
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'š' => Some('s'),
+            'ž' => Some('z'),
+            'č' | 'ć' => Some('c'),
+            'đ' => Some('d'),
+            c if c.is_ascii_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn parse_month_name(value: &str) -> Option<u8> {
+    let value = normalize_month_name(value);
+
+    if let Ok(month) = value.parse::<u8>() {
+        return (1..=12).contains(&month).then_some(month);
+    }
+
+    let find_month = |value: &str| {
+        MONTH_NAME_ALIASES
+            .iter()
+            .find(|(_, aliases)| aliases.contains(&value))
+            .map(|(month, _)| *month)
+    };
+
+    // Bosnian genitive, as in "27. redžeba"
+    find_month(&value).or_else(|| value.strip_suffix('a').and_then(find_month))
+}
+
+pub struct HijriCalendar {
+    method: CalendarMethod,
+    i18n: Arc<I18n>,
+    cross_check: Option<Arc<HijriApi>>,
+}
+
+impl HijriCalendar {
+    pub fn new(method: CalendarMethod, i18n: Arc<I18n>) -> Self {
+        Self {
+            method,
+            i18n,
+            cross_check: None,
+        }
+    }
+
+    /// Compares every calculated date with the one returned by the API in the background and
+    /// logs the differences
+    pub fn with_cross_check(mut self, api: Arc<HijriApi>) -> Self {
+        self.cross_check = Some(api);
+        self
+    }
+
+    pub fn days_in_month(&self, year: i32, month: u8) -> Option<u8> {
+        if !(1..=12).contains(&month) || year < 1 {
+            return None;
+        }
+
+        Some(match self.method {
+            CalendarMethod::Tabular => tabular::days_in_month(year, month),
+            CalendarMethod::UmmAlQura => umm_al_qura::days_in_month(year, month),
+        })
+    }
+
+    pub fn to_hijri(&self, date: NaiveDate) -> Result<HijriYmd, AppErrorKind> {
+        let hijri_date = match self.method {
+            CalendarMethod::Tabular => tabular::to_hijri(date),
+            CalendarMethod::UmmAlQura => umm_al_qura::to_hijri(date),
+        }
+        .ok_or_else(|| {
+            log::error!("Date {} is out of the supported range", date);
+            AppErrorKind::DateConversion
+        })?;
+
+        if hijri_date.year < 1 {
+            log::error!("Date {} is before the Hijri epoch", date);
+            return Err(AppErrorKind::DateConversion);
+        }
+
+        Ok(hijri_date)
+    }
+
+    pub fn to_gregorian(&self, date: &HijriYmd) -> Result<NaiveDate, AppErrorKind> {
+        match self.days_in_month(date.year, date.month) {
+            Some(days) if (1..=days).contains(&date.day) => {}
+            _ => {
+                log::error!("Invalid Hijri date: {}", date);
+                return Err(AppErrorKind::DateConversion);
+            }
+        }
+
+        let gregorian_date = match self.method {
+            CalendarMethod::Tabular => tabular::to_gregorian(date),
+            CalendarMethod::UmmAlQura => umm_al_qura::to_gregorian(date),
+        };
+
+        gregorian_date.ok_or_else(|| {
+            log::error!("Hijri date {} is out of the supported range", date);
+            AppErrorKind::DateConversion
+        })
+    }
+
+    pub async fn calculate_hijri_date(
+        &self,
+        date: NaiveDate,
+    ) -> Result<CurrentDateResponse, AppErrorKind> {
+        let hijri_date = self.to_hijri(date)?;
+
+        // The answer doesn't wait for the API, which is only asked in the background
+        if let Some(api) = &self.cross_check {
+            let api = Arc::clone(api);
+
+            tokio::spawn(async move {
+                match api.fetch_hijri_date(date).await {
+                    Ok(response)
+                        if response.year == hijri_date.year.to_string()
+                            && response.month_number == hijri_date.month
+                            && response.day_number == hijri_date.day => {}
+                    Ok(response) => {
+                        log::warn!(
+                            "Calculated Hijri date {} for {} differs from the API date {}",
+                            hijri_date,
+                            date,
+                            response
+                        );
+                    }
+                    Err(_err) => {
+                        log::warn!("Could not cross-check the Hijri date for {}", date);
+                    }
+                }
+            });
+        }
+
+        Ok(CurrentDateResponse::from_hijri_ymd(&hijri_date, &self.i18n))
+    }
+}
+
+impl HijriDateProvider for HijriCalendar {
+    fn name(&self) -> &'static str {
+        "offline"
+    }
+
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(self.calculate_hijri_date(date))
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::calendar::HijriYmd;
+
+/// Day number (as returned by `NaiveDate::num_days_from_ce`) of 1 Muharram 1 AH,
+/// which is Friday, 16 July 622 in the Julian calendar.
+const EPOCH: i32 = 227_015;
+
+fn days_before_year(year: i32) -> Option<i32> {
+    // Leap years of the 30-year cycle are 2, 5, 7, 10, 13, 16, 18, 21, 24, 26 and 29
+    let leap_days = (3 + 11 * i64::from(year)).div_euclid(30);
+
+    year.checked_sub(1)?
+        .checked_mul(354)?
+        .checked_add(i32::try_from(leap_days).ok()?)
+}
+
+fn days_before_month(month: u8) -> i32 {
+    // Odd months have 30 days and even months have 29 days
+    (59 * (month as i32 - 1) + 1) / 2
+}
+
+fn day_number(year: i32, month: u8, day: u8) -> Option<i32> {
+    days_before_year(year)?.checked_add(EPOCH - 1 + days_before_month(month) + day as i32)
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (14 + 11 * i64::from(year)).rem_euclid(30) < 11
+}
+
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        12 if is_leap_year(year) => 30,
+        _ if month % 2 == 1 => 30,
+        _ => 29,
+    }
+}
+
+pub fn to_hijri(date: NaiveDate) -> Option<HijriYmd> {
+    let days = date.num_days_from_ce();
+    let year = (days - EPOCH)
+        .checked_mul(30)?
+        .checked_add(10_646)?
+        .div_euclid(10_631);
+    let month = (1..=12u8)
+        .rev()
+        .find(|month| day_number(year, *month, 1).is_some_and(|start| start <= days))
+        .unwrap_or(1);
+    let day = (days - day_number(year, month, 1)? + 1) as u8;
+
+    Some(HijriYmd { year, month, day })
+}
+
+pub fn to_gregorian(date: &HijriYmd) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(day_number(date.year, date.month, date.day)?)
+}
@@ -0,0 +1,88 @@
+use chrono::NaiveDate;
+
+use crate::calendar::{
+    HijriYmd, tabular,
+    umm_al_qura_data::{DATA, STARTING_YEAR},
+};
+
+struct UmmAlQuraYear {
+    start: NaiveDate,
+    month_lengths: &'static str,
+}
+
+impl UmmAlQuraYear {
+    fn days_in_month(&self, month: u8) -> u8 {
+        match self.month_lengths.as_bytes().get(month as usize - 1) {
+            Some(b'1') => 30,
+            _ => 29,
+        }
+    }
+}
+
+fn year_data(year: i32) -> Option<UmmAlQuraYear> {
+    let index = usize::try_from(year - STARTING_YEAR).ok()?;
+    let (year, month, day, month_lengths) = DATA.get(index)?;
+
+    Some(UmmAlQuraYear {
+        start: NaiveDate::from_ymd_opt(*year, *month, *day)?,
+        month_lengths,
+    })
+}
+
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match year_data(year) {
+        Some(data) => data.days_in_month(month),
+        None => tabular::days_in_month(year, month),
+    }
+}
+
+pub fn to_hijri(date: NaiveDate) -> Option<HijriYmd> {
+    let years_started = DATA.partition_point(|(year, month, day, _)| {
+        NaiveDate::from_ymd_opt(*year, *month, *day).is_some_and(|start| start <= date)
+    });
+
+    // Dates before the table fall back to the tabular calendar
+    if years_started == 0 {
+        return tabular::to_hijri(date);
+    }
+
+    let year = STARTING_YEAR + years_started as i32 - 1;
+    let data = match year_data(year) {
+        Some(data) => data,
+        None => return tabular::to_hijri(date),
+    };
+
+    let mut remaining_days = (date - data.start).num_days();
+    let mut month = 1;
+
+    while month < 12 && remaining_days >= data.days_in_month(month) as i64 {
+        remaining_days -= data.days_in_month(month) as i64;
+        month += 1;
+    }
+
+    // Dates after the table fall back to the tabular calendar as well
+    if remaining_days >= data.days_in_month(month) as i64 {
+        return tabular::to_hijri(date);
+    }
+
+    Some(HijriYmd {
+        year,
+        month,
+        day: remaining_days as u8 + 1,
+    })
+}
+
+pub fn to_gregorian(date: &HijriYmd) -> Option<NaiveDate> {
+    let data = match year_data(date.year) {
+        Some(data) => data,
+        None => return tabular::to_gregorian(date),
+    };
+
+    let days_before_month: i64 = (1..date.month)
+        .map(|month| data.days_in_month(month) as i64)
+        .sum();
+
+    data.start.checked_add_signed(chrono::Duration::days(
+        days_before_month + date.day as i64 - 1,
+    ))
+}
@@ -0,0 +1,312 @@
+//! Umm al-Qura month lengths for the years 1300-1600 AH, obtained from ICU4C:
+//! <https://github.com/unicode-org/icu/blob/1bf6bf774dbc8c6c2051963a81100ea1114b497f/icu4c/source/i18n/islamcal.cpp#L264>
+//!
+//! Every entry holds the Gregorian date of 1 Muharram and the lengths of the twelve months,
+//! where `1` marks a month of 30 days and `0` a month of 29 days.
+
+pub const STARTING_YEAR: i32 = 1300;
+
+#[rustfmt::skip]
+pub const DATA: &[(i32, u32, u32, &str)] = &[
+    (1882, 11, 12, "101010101010"), // 1300
+    (1883, 11, 1, "110101010100"), // 1301
+    (1884, 10, 20, "111011001001"), // 1302
+    (1885, 10, 10, "011011010100"), // 1303
+    (1886, 9, 29, "011011101010"), // 1304
+    (1887, 9, 19, "001101101100"), // 1305
+    (1888, 9, 7, "101010101101"), // 1306
+    (1889, 8, 28, "010101010101"), // 1307
+    (1890, 8, 17, "011010101001"), // 1308
+    (1891, 8, 6, "011110010010"), // 1309
+    (1892, 7, 25, "101110101001"), // 1310
+    (1893, 7, 15, "010111010100"), // 1311
+    (1894, 7, 4, "101011011010"), // 1312
+    (1895, 6, 24, "010101011100"), // 1313
+    (1896, 6, 12, "110100101101"), // 1314
+    (1897, 6, 2, "011010010101"), // 1315
+    (1898, 5, 22, "011101001010"), // 1316
+    (1899, 5, 11, "101101010100"), // 1317
+    (1900, 4, 30, "101101101010"), // 1318
+    (1901, 4, 20, "010110101101"), // 1319
+    (1902, 4, 10, "010010101110"), // 1320
+    (1903, 3, 30, "101001001111"), // 1321
+    (1904, 3, 19, "010100010111"), // 1322
+    (1905, 3, 8, "011010001011"), // 1323
+    (1906, 2, 25, "011010100101"), // 1324
+    (1907, 2, 14, "101011010101"), // 1325
+    (1908, 2, 4, "001011010110"), // 1326
+    (1909, 1, 23, "100101011011"), // 1327
+    (1910, 1, 13, "010010011101"), // 1328
+    (1911, 1, 2, "101001001101"), // 1329
+    (1911, 12, 22, "110100100110"), // 1330
+    (1912, 12, 10, "110110010101"), // 1331
+    (1913, 11, 30, "010110101100"), // 1332
+    (1914, 11, 19, "100110110110"), // 1333
+    (1915, 11, 9, "001010111010"), // 1334
+    (1916, 10, 28, "101001011011"), // 1335
+    (1917, 10, 18, "010100101011"), // 1336
+    (1918, 10, 7, "101010010101"), // 1337
+    (1919, 9, 26, "011011001010"), // 1338
+    (1920, 9, 14, "101011101001"), // 1339
+    (1921, 9, 4, "001011110100"), // 1340
+    (1922, 8, 24, "100101110110"), // 1341
+    (1923, 8, 14, "001010110110"), // 1342
+    (1924, 8, 2, "100101010110"), // 1343
+    (1925, 7, 22, "101011001010"), // 1344
+    (1926, 7, 11, "101110100100"), // 1345
+    (1927, 6, 30, "101111010010"), // 1346
+    (1928, 6, 19, "010111011001"), // 1347
+    (1929, 6, 9, "001011011100"), // 1348
+    (1930, 5, 29, "100101101101"), // 1349
+    (1931, 5, 19, "010101001101"), // 1350
+    (1932, 5, 7, "101010100101"), // 1351
+    (1933, 4, 26, "101101010010"), // 1352
+    (1934, 4, 15, "101110100101"), // 1353
+    (1935, 4, 5, "010110110100"), // 1354
+    (1936, 3, 24, "100110110110"), // 1355
+    (1937, 3, 14, "010101010111"), // 1356
+    (1938, 3, 4, "001010010111"), // 1357
+    (1939, 2, 21, "010101001011"), // 1358
+    (1940, 2, 10, "011010100011"), // 1359
+    (1941, 1, 29, "011101010010"), // 1360
+    (1942, 1, 18, "101101100101"), // 1361
+    (1943, 1, 8, "010101101010"), // 1362
+    (1943, 12, 28, "101010101011"), // 1363
+    (1944, 12, 17, "010100101011"), // 1364
+    (1945, 12, 6, "110010010101"), // 1365
+    (1946, 11, 25, "110101001010"), // 1366
+    (1947, 11, 14, "110110100101"), // 1367
+    (1948, 11, 3, "010111001010"), // 1368
+    (1949, 10, 23, "101011010110"), // 1369
+    (1950, 10, 13, "100101010111"), // 1370
+    (1951, 10, 3, "010010101011"), // 1371
+    (1952, 9, 21, "100101001011"), // 1372
+    (1953, 9, 10, "101010100101"), // 1373
+    (1954, 8, 30, "101101010010"), // 1374
+    (1955, 8, 19, "101101101010"), // 1375
+    (1956, 8, 8, "010101110101"), // 1376
+    (1957, 7, 29, "001001110110"), // 1377
+    (1958, 7, 18, "100010110111"), // 1378
+    (1959, 7, 8, "010001011011"), // 1379
+    (1960, 6, 26, "010101010101"), // 1380
+    (1961, 6, 15, "010110101001"), // 1381
+    (1962, 6, 4, "010110110100"), // 1382
+    (1963, 5, 24, "100111011010"), // 1383
+    (1964, 5, 13, "010011011101"), // 1384
+    (1965, 5, 3, "001001101110"), // 1385
+    (1966, 4, 22, "100100110110"), // 1386
+    (1967, 4, 11, "101010101010"), // 1387
+    (1968, 3, 30, "110101010100"), // 1388
+    (1969, 3, 19, "110110110010"), // 1389
+    (1970, 3, 9, "010111010101"), // 1390
+    (1971, 2, 27, "001011011010"), // 1391
+    (1972, 2, 16, "100101011011"), // 1392
+    (1973, 2, 5, "010010101011"), // 1393
+    (1974, 1, 25, "101001010101"), // 1394
+    (1975, 1, 14, "101101001001"), // 1395
+    (1976, 1, 3, "101101100100"), // 1396
+    (1976, 12, 22, "101101110001"), // 1397
+    (1977, 12, 12, "010110110100"), // 1398
+    (1978, 12, 1, "101010110101"), // 1399
+    (1979, 11, 21, "101001010101"), // 1400
+    (1980, 11, 9, "110100100101"), // 1401
+    (1981, 10, 29, "111010010010"), // 1402
+    (1982, 10, 18, "111011001001"), // 1403
+    (1983, 10, 8, "011011010100"), // 1404
+    (1984, 9, 26, "101011101001"), // 1405
+    (1985, 9, 16, "100101101011"), // 1406
+    (1986, 9, 6, "010010101011"), // 1407
+    (1987, 8, 26, "101010010011"), // 1408
+    (1988, 8, 14, "110101001001"), // 1409
+    (1989, 8, 3, "110110100100"), // 1410
+    (1990, 7, 23, "110110110010"), // 1411
+    (1991, 7, 13, "101010111001"), // 1412
+    (1992, 7, 2, "010010111010"), // 1413
+    (1993, 6, 21, "101001011011"), // 1414
+    (1994, 6, 11, "010100101011"), // 1415
+    (1995, 5, 31, "101010010101"), // 1416
+    (1996, 5, 19, "101100101010"), // 1417
+    (1997, 5, 8, "101101010101"), // 1418
+    (1998, 4, 28, "010101011100"), // 1419
+    (1999, 4, 17, "010010111101"), // 1420
+    (2000, 4, 6, "001000111101"), // 1421
+    (2001, 3, 26, "100100011101"), // 1422
+    (2002, 3, 15, "101010010101"), // 1423
+    (2003, 3, 4, "101101001010"), // 1424
+    (2004, 2, 21, "101101011010"), // 1425
+    (2005, 2, 10, "010101101101"), // 1426
+    (2006, 1, 31, "001010110110"), // 1427
+    (2007, 1, 20, "100100111011"), // 1428
+    (2008, 1, 10, "010010011011"), // 1429
+    (2008, 12, 29, "011001010101"), // 1430
+    (2009, 12, 18, "011010101001"), // 1431
+    (2010, 12, 7, "011101010100"), // 1432
+    (2011, 11, 26, "101101101010"), // 1433
+    (2012, 11, 15, "010101101100"), // 1434
+    (2013, 11, 4, "101010101101"), // 1435
+    (2014, 10, 25, "010101010101"), // 1436
+    (2015, 10, 14, "101100101001"), // 1437
+    (2016, 10, 2, "101110010010"), // 1438
+    (2017, 9, 21, "101110101001"), // 1439
+    (2018, 9, 11, "010111010100"), // 1440
+    (2019, 8, 31, "101011011010"), // 1441
+    (2020, 8, 20, "010101011010"), // 1442
+    (2021, 8, 9, "101010101011"), // 1443
+    (2022, 7, 30, "010110010101"), // 1444
+    (2023, 7, 19, "011101001001"), // 1445
+    (2024, 7, 7, "011101100100"), // 1446
+    (2025, 6, 26, "101110101010"), // 1447
+    (2026, 6, 16, "010110110101"), // 1448
+    (2027, 6, 6, "001010110110"), // 1449
+    (2028, 5, 25, "101001010110"), // 1450
+    (2029, 5, 14, "111001001101"), // 1451
+    (2030, 5, 4, "101100100101"), // 1452
+    (2031, 4, 23, "101101010010"), // 1453
+    (2032, 4, 11, "101101101010"), // 1454
+    (2033, 4, 1, "010110101101"), // 1455
+    (2034, 3, 22, "001010101110"), // 1456
+    (2035, 3, 11, "100100101111"), // 1457
+    (2036, 2, 29, "010010010111"), // 1458
+    (2037, 2, 17, "011001001011"), // 1459
+    (2038, 2, 6, "011010100101"), // 1460
+    (2039, 1, 26, "011010101100"), // 1461
+    (2040, 1, 15, "101011010110"), // 1462
+    (2041, 1, 4, "010101011101"), // 1463
+    (2041, 12, 25, "010010011101"), // 1464
+    (2042, 12, 14, "101001001101"), // 1465
+    (2043, 12, 3, "110100010110"), // 1466
+    (2044, 11, 21, "110110010101"), // 1467
+    (2045, 11, 11, "010110101010"), // 1468
+    (2046, 10, 31, "010110110101"), // 1469
+    (2047, 10, 21, "001011011010"), // 1470
+    (2048, 10, 9, "100101011011"), // 1471
+    (2049, 9, 29, "010010101101"), // 1472
+    (2050, 9, 18, "010110010101"), // 1473
+    (2051, 9, 7, "011011001010"), // 1474
+    (2052, 8, 26, "011011100100"), // 1475
+    (2053, 8, 15, "101011101010"), // 1476
+    (2054, 8, 5, "010011110101"), // 1477
+    (2055, 7, 26, "001010110110"), // 1478
+    (2056, 7, 14, "100101010110"), // 1479
+    (2057, 7, 3, "101010101010"), // 1480
+    (2058, 6, 22, "101101010100"), // 1481
+    (2059, 6, 11, "101111010010"), // 1482
+    (2060, 5, 31, "010111011001"), // 1483
+    (2061, 5, 21, "001011101010"), // 1484
+    (2062, 5, 10, "100101101101"), // 1485
+    (2063, 4, 30, "010010101101"), // 1486
+    (2064, 4, 18, "101010010101"), // 1487
+    (2065, 4, 7, "101101001010"), // 1488
+    (2066, 3, 27, "101110100101"), // 1489
+    (2067, 3, 17, "010110110010"), // 1490
+    (2068, 3, 5, "100110110101"), // 1491
+    (2069, 2, 23, "010011010110"), // 1492
+    (2070, 2, 12, "101010010111"), // 1493
+    (2071, 2, 2, "010101000111"), // 1494
+    (2072, 1, 22, "011010010011"), // 1495
+    (2073, 1, 10, "011101001001"), // 1496
+    (2073, 12, 30, "101101010101"), // 1497
+    (2074, 12, 20, "010101101010"), // 1498
+    (2075, 12, 9, "101001101011"), // 1499
+    (2076, 11, 28, "010100101011"), // 1500
+    (2077, 11, 17, "101010001011"), // 1501
+    (2078, 11, 6, "110101000110"), // 1502
+    (2079, 10, 26, "110110100011"), // 1503
+    (2080, 10, 15, "010111001010"), // 1504
+    (2081, 10, 4, "101011010110"), // 1505
+    (2082, 9, 24, "010011011011"), // 1506
+    (2083, 9, 14, "001001101011"), // 1507
+    (2084, 9, 2, "100101001011"), // 1508
+    (2085, 8, 22, "101010100101"), // 1509
+    (2086, 8, 11, "101101010010"), // 1510
+    (2087, 7, 31, "101101101001"), // 1511
+    (2088, 7, 20, "010101110101"), // 1512
+    (2089, 7, 10, "000101110110"), // 1513
+    (2090, 6, 29, "100010110111"), // 1514
+    (2091, 6, 19, "001001011011"), // 1515
+    (2092, 6, 7, "010100101011"), // 1516
+    (2093, 5, 27, "010101100101"), // 1517
+    (2094, 5, 16, "010110110100"), // 1518
+    (2095, 5, 5, "100111011010"), // 1519
+    (2096, 4, 24, "010011101101"), // 1520
+    (2097, 4, 14, "000101101101"), // 1521
+    (2098, 4, 3, "100010110110"), // 1522
+    (2099, 3, 23, "101010100110"), // 1523
+    (2100, 3, 12, "110101010010"), // 1524
+    (2101, 3, 1, "110110101001"), // 1525
+    (2102, 2, 19, "010111010100"), // 1526
+    (2103, 2, 8, "101011011010"), // 1527
+    (2104, 1, 29, "100101011011"), // 1528
+    (2105, 1, 18, "010010101011"), // 1529
+    (2106, 1, 7, "011001010011"), // 1530
+    (2106, 12, 27, "011100101001"), // 1531
+    (2107, 12, 16, "011101100010"), // 1532
+    (2108, 12, 4, "101110101001"), // 1533
+    (2109, 11, 24, "010110110010"), // 1534
+    (2110, 11, 13, "101010110101"), // 1535
+    (2111, 11, 3, "010101010101"), // 1536
+    (2112, 10, 22, "101100100101"), // 1537
+    (2113, 10, 11, "110110010010"), // 1538
+    (2114, 9, 30, "111011001001"), // 1539
+    (2115, 9, 20, "011011010010"), // 1540
+    (2116, 9, 8, "101011101001"), // 1541
+    (2117, 8, 29, "010101101011"), // 1542
+    (2118, 8, 19, "010010101011"), // 1543
+    (2119, 8, 8, "101001010101"), // 1544
+    (2120, 7, 27, "110100101001"), // 1545
+    (2121, 7, 16, "110101010100"), // 1546
+    (2122, 7, 5, "110110101010"), // 1547
+    (2123, 6, 25, "100110110101"), // 1548
+    (2124, 6, 14, "010010111010"), // 1549
+    (2125, 6, 3, "101000111011"), // 1550
+    (2126, 5, 24, "010010011011"), // 1551
+    (2127, 5, 13, "101001001101"), // 1552
+    (2128, 5, 1, "101010101010"), // 1553
+    (2129, 4, 20, "101011010101"), // 1554
+    (2130, 4, 10, "001011011010"), // 1555
+    (2131, 3, 30, "100101011101"), // 1556
+    (2132, 3, 19, "010001011110"), // 1557
+    (2133, 3, 8, "101000101110"), // 1558
+    (2134, 2, 25, "110010011010"), // 1559
+    (2135, 2, 14, "110101010101"), // 1560
+    (2136, 2, 4, "011010110010"), // 1561
+    (2137, 1, 23, "011010111001"), // 1562
+    (2138, 1, 13, "010010111010"), // 1563
+    (2139, 1, 2, "101001011101"), // 1564
+    (2139, 12, 23, "010100101101"), // 1565
+    (2140, 12, 11, "101010010101"), // 1566
+    (2141, 11, 30, "101101010010"), // 1567
+    (2142, 11, 19, "101110101000"), // 1568
+    (2143, 11, 8, "101110110100"), // 1569
+    (2144, 10, 28, "010110111001"), // 1570
+    (2145, 10, 18, "001011011010"), // 1571
+    (2146, 10, 7, "100101011010"), // 1572
+    (2147, 9, 26, "101101001010"), // 1573
+    (2148, 9, 14, "110110100100"), // 1574
+    (2149, 9, 3, "111011010001"), // 1575
+    (2150, 8, 24, "011011101000"), // 1576
+    (2151, 8, 13, "101101101010"), // 1577
+    (2152, 8, 2, "010101101101"), // 1578
+    (2153, 7, 23, "010100110101"), // 1579
+    (2154, 7, 12, "011010010101"), // 1580
+    (2155, 7, 1, "110101001010"), // 1581
+    (2156, 6, 19, "110110101000"), // 1582
+    (2157, 6, 8, "110111010100"), // 1583
+    (2158, 5, 29, "011011011010"), // 1584
+    (2159, 5, 19, "010101011011"), // 1585
+    (2160, 5, 8, "001010011101"), // 1586
+    (2161, 4, 27, "011000101011"), // 1587
+    (2162, 4, 16, "101100010101"), // 1588
+    (2163, 4, 5, "101101001010"), // 1589
+    (2164, 3, 24, "101110010101"), // 1590
+    (2165, 3, 14, "010110101010"), // 1591
+    (2166, 3, 3, "101010101110"), // 1592
+    (2167, 2, 21, "100100101110"), // 1593
+    (2168, 2, 10, "110010001111"), // 1594
+    (2169, 1, 30, "010100100111"), // 1595
+    (2170, 1, 19, "011010010101"), // 1596
+    (2171, 1, 8, "011010101010"), // 1597
+    (2171, 12, 28, "101011010110"), // 1598
+    (2172, 12, 17, "010101011101"), // 1599
+    (2173, 12, 7, "001010011101"), // 1600
+];
@@ -5,6 +5,34 @@ use teloxide::macros::BotCommands;
 pub enum Command {
     Help,
     Date,
+    #[command(aliases = ["pretvori", "konvertuj"], hide_aliases)]
+    Convert(String),
+    #[command(aliases = ["kalendar"], hide_aliases)]
+    Calendar,
+    #[command(aliases = ["datumi"], hide_aliases)]
+    Events(String),
+    #[command(aliases = ["vremenskazona", "zona"], hide_aliases)]
+    Timezone(String),
+    #[command(aliases = ["lokacija"], hide_aliases)]
+    Location(String),
+    #[command(rename = "vaktija", aliases = ["prayertimes"], hide_aliases)]
+    PrayerTimes(String),
+    #[command(aliases = ["podsjetnik"], hide_aliases)]
+    Reminder(String),
+    #[command(aliases = ["sunnet"], hide_aliases)]
+    Sunnah(String),
+    #[command(rename = "sevval", aliases = ["shawwal"], hide_aliases)]
+    Shawwal(String),
+    #[command(rename = "post", aliases = ["fasting"], hide_aliases)]
+    Fasting(String),
+    #[command(aliases = ["postavke"], hide_aliases)]
+    Settings,
+    #[command(aliases = ["vrijeme"], hide_aliases)]
+    Time(String),
+    #[command(aliases = ["odjava"], hide_aliases)]
+    Stop,
     #[command(hide)]
     Start,
+    #[command(hide)]
+    MonthStart(String),
 }
@@ -0,0 +1,249 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    api::CurrentDateResponse,
+    calendar::{HijriCalendar, HijriYmd, normalize_month_name, parse_month_name},
+    error::AppErrorKind,
+    provider::HijriDateProvider,
+};
+
+/// Numeric dates with a year below this one are read as Hijri dates
+const HIJRI_YEAR_LIMIT: i32 = 1700;
+
+/// Years of the Gregorian dates which can be converted
+const GREGORIAN_YEARS: RangeInclusive<i32> = 1..=9999;
+
+/// Years of the Hijri dates which can be converted, ending where the Gregorian ones do
+const HIJRI_YEARS: RangeInclusive<i32> = 1..=9665;
+
+/// Words marking a date as Hijri, normalized with [`normalize_month_name`]
+const HIJRI_MARKERS: [&str; 6] = ["h", "ah", "hijri", "hidzri", "hidzretske", "hidzretska"];
+
+/// Words which are allowed around a date, but carry no meaning
+const FILLER_WORDS: [&str; 3] = ["g", "god", "godine"];
+
+/// Prefixes of the Gregorian month names in Bosnian and English
+const GREGORIAN_MONTH_PREFIXES: [(u32, &[&str]); 12] = [
+    (1, &["jan"]),
+    (2, &["feb"]),
+    (3, &["mar"]),
+    (4, &["apr"]),
+    (5, &["maj", "may"]),
+    (6, &["jun"]),
+    (7, &["jul"]),
+    (8, &["aug", "avg"]),
+    (9, &["sep"]),
+    (10, &["okt", "oct"]),
+    (11, &["nov"]),
+    (12, &["dec"]),
+];
+
+fn parse_gregorian_month_name(value: &str) -> Option<u32> {
+    let value = normalize_month_name(value);
+
+    if value.len() < 3 {
+        return None;
+    }
+
+    GREGORIAN_MONTH_PREFIXES
+        .iter()
+        .find(|(_, prefixes)| prefixes.iter().any(|prefix| value.starts_with(prefix)))
+        .map(|(month, _)| *month)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateQuery {
+    Gregorian(NaiveDate),
+    Hijri(HijriYmd),
+}
+
+enum MonthToken {
+    Number(u32),
+    Gregorian(u32),
+    Hijri(u8),
+}
+
+impl DateQuery {
+    /// Parses dates such as `1995-07-11`, `11.07.1995.`, `11. jula 1995`, `July 11, 1995`,
+    /// `27. redžeb 1448`, `27.07.1448` or `1448-07-27 h`. Numeric dates are Hijri when their
+    /// year is below 1700 or when they are followed by `h`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut hijri_marker = false;
+        let mut tokens = Vec::new();
+
+        for token in input
+            .split(|c: char| c.is_whitespace() || ".,/-".contains(c))
+            .filter(|token| !token.is_empty())
+        {
+            let normalized = normalize_month_name(token);
+
+            if HIJRI_MARKERS.contains(&normalized.as_str()) {
+                hijri_marker = true;
+            } else if !FILLER_WORDS.contains(&normalized.as_str()) {
+                tokens.push(token);
+            }
+        }
+
+        if tokens.len() != 3 {
+            return None;
+        }
+
+        let numbers: Vec<Option<i32>> = tokens.iter().map(|token| token.parse().ok()).collect();
+        let (day, month, year) = match numbers.iter().position(Option::is_none) {
+            // Numeric dates are either year first or day first
+            None => {
+                let [first, second, third] = [numbers[0]?, numbers[1]?, numbers[2]?];
+
+                if tokens[0].len() >= 3 {
+                    (third, MonthToken::Number(second as u32), first)
+                } else {
+                    (first, MonthToken::Number(second as u32), third)
+                }
+            }
+            Some(index) => {
+                let month = match parse_month_name(tokens[index]) {
+                    Some(month) => MonthToken::Hijri(month),
+                    None => MonthToken::Gregorian(parse_gregorian_month_name(tokens[index])?),
+                };
+                let mut numbers = numbers
+                    .iter()
+                    .enumerate()
+                    .filter(|(position, _)| *position != index)
+                    .map(|(_, number)| *number);
+                let (first, second) = (numbers.next()??, numbers.next()??);
+
+                // The year is the last number, unless the day comes last, as in `1448 redžeb 27`
+                if first > 31 {
+                    (second, month, first)
+                } else {
+                    (first, month, second)
+                }
+            }
+        };
+
+        let day = u8::try_from(day).ok()?;
+
+        let query = match month {
+            MonthToken::Number(month) if hijri_marker || year < HIJRI_YEAR_LIMIT => {
+                Some(DateQuery::Hijri(HijriYmd {
+                    year,
+                    month: u8::try_from(month)
+                        .ok()
+                        .filter(|month| (1..=12).contains(month))?,
+                    day,
+                }))
+            }
+            MonthToken::Hijri(month) => Some(DateQuery::Hijri(HijriYmd { year, month, day })),
+            MonthToken::Number(month) | MonthToken::Gregorian(month) if !hijri_marker => {
+                NaiveDate::from_ymd_opt(year, month, day as u32).map(DateQuery::Gregorian)
+            }
+            _ => None,
+        }?;
+
+        let supported = match query {
+            DateQuery::Gregorian(date) => GREGORIAN_YEARS.contains(&date.year()),
+            DateQuery::Hijri(date) => HIJRI_YEARS.contains(&date.year),
+        };
+
+        supported.then_some(query)
+    }
+}
+
+/// Converts the dates in both directions. Gregorian dates are answered by the provider, so the
+/// overrides and the announced month starts are followed. Hijri dates are calculated by the
+/// calendar first and then shifted when the provider starts the month on another day.
+pub struct DateConverter {
+    calendar: Arc<HijriCalendar>,
+    provider: Arc<dyn HijriDateProvider>,
+}
+
+impl DateConverter {
+    pub fn new(calendar: Arc<HijriCalendar>, provider: Arc<dyn HijriDateProvider>) -> Self {
+        Self { calendar, provider }
+    }
+
+    pub async fn convert(
+        &self,
+        query: DateQuery,
+    ) -> Result<(NaiveDate, CurrentDateResponse), AppErrorKind> {
+        match query {
+            DateQuery::Gregorian(date) => {
+                let response = self.provider.get_hijri_date(date).await?;
+                Ok((date, response))
+            }
+            DateQuery::Hijri(date) => self.hijri_to_gregorian(&date).await,
+        }
+    }
+
+    /// Gregorian date of the first day of a Hijri month and the number of its days
+    pub async fn month_bounds(
+        &self,
+        year: i32,
+        month: u8,
+    ) -> Result<(NaiveDate, u8), AppErrorKind> {
+        let (start, _) = self
+            .hijri_to_gregorian(&HijriYmd {
+                year,
+                month,
+                day: 1,
+            })
+            .await?;
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+
+        let days = match self
+            .hijri_to_gregorian(&HijriYmd {
+                year: next_year,
+                month: next_month,
+                day: 1,
+            })
+            .await
+        {
+            Ok((next_start, _)) if (29..=30).contains(&(next_start - start).num_days()) => {
+                (next_start - start).num_days() as u8
+            }
+            _ => self
+                .calendar
+                .days_in_month(year, month)
+                .ok_or(AppErrorKind::DateConversion)?,
+        };
+
+        Ok((start, days))
+    }
+
+    async fn hijri_to_gregorian(
+        &self,
+        date: &HijriYmd,
+    ) -> Result<(NaiveDate, CurrentDateResponse), AppErrorKind> {
+        let gregorian_date = self.calendar.to_gregorian(date)?;
+        let response = self.provider.get_hijri_date(gregorian_date).await?;
+
+        let answered_date = HijriYmd {
+            year: response.year.parse().unwrap_or_default(),
+            month: response.month_number,
+            day: response.day_number,
+        };
+
+        if answered_date == *date {
+            return Ok((gregorian_date, response));
+        }
+
+        // An answer the calendar can't place, e.g. the 30th of a month of 29 days, gives no shift
+        let answered_gregorian_date = self.calendar.to_gregorian(&answered_date)?;
+
+        let gregorian_date = gregorian_date + (gregorian_date - answered_gregorian_date);
+        log::debug!(
+            "Provider starts the month of {} on another day, shifting it to {}",
+            date,
+            gregorian_date
+        );
+        let response = self.provider.get_hijri_date(gregorian_date).await?;
+
+        Ok((gregorian_date, response))
+    }
+}
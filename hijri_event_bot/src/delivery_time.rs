@@ -0,0 +1,51 @@
+use chrono::NaiveTime;
+use sqlx::{Pool, Postgres, types::Uuid};
+
+use crate::error::AppErrorKind;
+
+/// Local time at which the user receives the fasting reminders, instead of the evening before
+/// the day of the fast
+pub async fn get_user_delivery_time(pool: &Pool<Postgres>, chat_id: i64) -> Option<NaiveTime> {
+    sqlx::query_scalar!(
+        "SELECT delivery_time FROM users WHERE chat_id = $1",
+        chat_id
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap_or_else(|e| {
+        log::error!("Failed to fetch the delivery time of {}: {}", chat_id, e);
+        None
+    })
+    .flatten()
+}
+
+/// Sets the delivery time of the user, or goes back to the evening when it's `None`
+pub async fn set_user_delivery_time(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    username: Option<String>,
+    delivery_time: Option<NaiveTime>,
+) -> Result<(), AppErrorKind> {
+    sqlx::query!(
+        "
+            INSERT INTO users (id, chat_id, username, delivery_time)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (chat_id)
+            DO UPDATE SET delivery_time = EXCLUDED.delivery_time
+        ",
+        Uuid::new_v4(),
+        chat_id,
+        username,
+        delivery_time
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to set the delivery time of {}: {}", chat_id, e);
+        AppErrorKind::DeliveryTime
+    })?;
+
+    log::info!("Delivery time of {} set to {:?}", chat_id, delivery_time);
+
+    Ok(())
+}
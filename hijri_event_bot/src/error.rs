@@ -1,4 +1,58 @@
+use crate::i18n::translation_key::TranslationKey;
+
+#[derive(Debug)]
 pub enum AppErrorKind {
     WhiteDaysMessage,
-    ApiRequest,
+    /// The API did not respond in time
+    ApiTimeout,
+    /// The API could not be reached or responded with a server error
+    ApiUnavailable,
+    /// The API rejected the request with a client error
+    ApiClientError,
+    /// The API responded with an unexpected format
+    ApiInvalidResponse,
+    /// The requests to the API are stopped after too many failures
+    ApiCircuitOpen,
+    DateConversion,
+    HijriDateOverride,
+    /// The provider has no answer for the date, e.g. there's no override for it, so the next
+    /// provider is asked
+    HijriDateNotFound,
+    HijriDateUnavailable,
+    MonthStartAnnouncement,
+    UserTimezone,
+    UserLocation,
+    EventReminder,
+    EventCatalog,
+    PrayerTimes,
+    PrayerReminder,
+    SunnahFastReminder,
+    ShawwalFasts,
+    RamadanReminder,
+    NotificationPreferences,
+    DeliveryTime,
+    Unsubscribe,
+    SchedulerInitialization,
+}
+
+impl AppErrorKind {
+    /// Whether repeating the same request later could succeed
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            AppErrorKind::ApiTimeout | AppErrorKind::ApiUnavailable
+        )
+    }
+
+    pub fn current_date_translation_key(&self) -> TranslationKey {
+        match self {
+            AppErrorKind::ApiTimeout
+            | AppErrorKind::ApiUnavailable
+            | AppErrorKind::ApiCircuitOpen => TranslationKey::ErrorApiUnavailable,
+            AppErrorKind::ApiClientError | AppErrorKind::ApiInvalidResponse => {
+                TranslationKey::ErrorApiInvalidResponse
+            }
+            _ => TranslationKey::ErrorCurrentDate,
+        }
+    }
 }
@@ -0,0 +1,263 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{
+    calendar::HijriYmd,
+    error::AppErrorKind,
+    i18n::{instance::I18n, translation_key::TranslationKey},
+};
+
+/// When an event is announced, relative to the evening at which its Hijri day starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "String")]
+pub enum LeadTime {
+    /// The evening before the day of the event
+    Evening,
+    /// The given number of days before that evening
+    DaysBefore(u32),
+}
+
+impl LeadTime {
+    pub fn days(&self) -> u32 {
+        match self {
+            LeadTime::Evening => 0,
+            LeadTime::DaysBefore(days) => *days,
+        }
+    }
+}
+
+impl FromStr for LeadTime {
+    type Err = AppErrorKind;
+
+    /// Parses lead times such as `evening` or `3 days`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_lowercase();
+
+        if value == "evening" {
+            return Ok(LeadTime::Evening);
+        }
+
+        match value.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [days, "day" | "days"] => match days.parse() {
+                Ok(0) => Ok(LeadTime::Evening),
+                Ok(days) => Ok(LeadTime::DaysBefore(days)),
+                Err(_e) => Err(AppErrorKind::EventCatalog),
+            },
+            _ => Err(AppErrorKind::EventCatalog),
+        }
+    }
+}
+
+impl TryFrom<String> for LeadTime {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .parse()
+            .map_err(|_e| format!("unknown lead time: {}", value))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IslamicEvent {
+    pub id: String,
+    /// Other names the event can be looked up by, e.g. in `/events ramazan`
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub month: u8,
+    pub day: u8,
+    /// Last day of the events lasting several days
+    pub end_day: Option<u8>,
+    /// Whether the night before the day is the significant part, as for Laylat al-Qadr
+    #[serde(default)]
+    pub night: bool,
+    #[serde(default)]
+    pub lead_times: Vec<LeadTime>,
+    name: HashMap<String, String>,
+    #[serde(default)]
+    text: HashMap<String, String>,
+}
+
+impl IslamicEvent {
+    pub fn last_day(&self) -> u8 {
+        self.end_day.unwrap_or(self.day)
+    }
+
+    pub fn falls_on(&self, month: u8, day: u8) -> bool {
+        self.month == month && (self.day..=self.last_day()).contains(&day)
+    }
+
+    /// First day of the event in the year of `today` if the event hasn't ended yet, or else in
+    /// the following year
+    pub fn next_start(&self, today: &HijriYmd) -> HijriYmd {
+        let year = if (self.month, self.last_day()) >= (today.month, today.day) {
+            today.year
+        } else {
+            today.year + 1
+        };
+
+        HijriYmd {
+            year,
+            month: self.month,
+            day: self.day,
+        }
+    }
+
+    /// Tells when the event starts, given the number of days until its Gregorian day. The
+    /// nights are counted to the evening before the day.
+    pub fn countdown(&self, days_until: i64, i18n: &I18n) -> String {
+        let days = if self.night {
+            days_until - 1
+        } else {
+            days_until
+        };
+
+        let key = match days {
+            ..0 => TranslationKey::EventCountdownOngoing,
+            0 if self.night => TranslationKey::EventCountdownTonight,
+            0 => TranslationKey::EventCountdownToday,
+            1 => TranslationKey::EventCountdownTomorrow,
+            days if days % 10 == 1 && days % 100 != 11 => TranslationKey::EventCountdownDay,
+            _ => TranslationKey::EventCountdownDays,
+        };
+
+        let mut args = HashMap::new();
+        args.insert("days", days.to_string());
+
+        i18n.t_with_args(&key, args)
+    }
+
+    pub fn name(&self, i18n: &I18n) -> String {
+        self.name
+            .get(i18n.language().code())
+            .cloned()
+            .unwrap_or(self.id.clone())
+    }
+
+    /// Text sent along with the notifications of the event
+    pub fn text(&self, i18n: &I18n) -> Option<String> {
+        self.text.get(i18n.language().code()).cloned()
+    }
+
+    /// Notification sent `days` days before the evening at which the event starts, on the
+    /// Gregorian `date`
+    pub fn announcement(&self, days: u32, date: NaiveDate, i18n: &I18n) -> String {
+        let key = match (self.night, days) {
+            (true, 0) => TranslationKey::EventReminderNight,
+            (false, 0) => TranslationKey::EventReminderEvening,
+            (true, _) => TranslationKey::EventReminderNightDaysBefore,
+            (false, _) => TranslationKey::EventReminderDaysBefore,
+        };
+
+        let mut args = HashMap::new();
+        args.insert("name", self.name(i18n));
+        args.insert("days", days.to_string());
+        args.insert("date", date.format("%d.%m.%Y.").to_string());
+
+        let mut message = i18n.t_with_args(&key, args);
+        if let Some(text) = self.text(i18n) {
+            message.push_str("\n\n");
+            message.push_str(&text);
+        }
+
+        message
+    }
+}
+
+#[derive(Deserialize)]
+struct EventCatalogFile {
+    events: Vec<IslamicEvent>,
+}
+
+/// Notable days of the Hijri year, read from `events.toml`
+pub struct EventCatalog {
+    events: Vec<IslamicEvent>,
+}
+
+impl EventCatalog {
+    pub fn new() -> Result<Self, AppErrorKind> {
+        EventCatalog::from_toml(include_str!("../events.toml"))
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, AppErrorKind> {
+        let file: EventCatalogFile = toml::from_str(content).map_err(|e| {
+            log::error!("Failed to parse the events catalog: {}", e);
+            AppErrorKind::EventCatalog
+        })?;
+
+        if let Some(event) = file.events.iter().find(|event| {
+            !(1..=12).contains(&event.month)
+                || !(1..=30).contains(&event.day)
+                || !(event.day..=30).contains(&event.last_day())
+        }) {
+            log::error!("Invalid date of the event {} in the catalog", event.id);
+            return Err(AppErrorKind::EventCatalog);
+        }
+
+        let mut events = file.events;
+        events.sort_by_key(|event| (event.month, event.day));
+
+        Ok(Self { events })
+    }
+
+    /// All the events, in the order they occur
+    pub fn events(&self) -> &[IslamicEvent] {
+        &self.events
+    }
+
+    /// Finds the event by its id or one of its aliases, ignoring the case
+    pub fn find(&self, query: &str) -> Option<&IslamicEvent> {
+        let query = query.trim().to_lowercase();
+
+        self.events.iter().find(|event| {
+            event.id == query
+                || event
+                    .aliases
+                    .iter()
+                    .any(|alias| alias.to_lowercase() == query)
+        })
+    }
+
+    /// Events along with their next start, in the order they start, the ongoing ones first
+    pub fn upcoming(&self, today: &HijriYmd) -> Vec<(&IslamicEvent, HijriYmd)> {
+        let mut events: Vec<(&IslamicEvent, HijriYmd)> = self
+            .events
+            .iter()
+            .map(|event| (event, event.next_start(today)))
+            .collect();
+        events.sort_by_key(|(_, start)| *start);
+
+        events
+    }
+
+    pub fn in_month(&self, month: u8) -> impl Iterator<Item = &IslamicEvent> {
+        self.events.iter().filter(move |event| event.month == month)
+    }
+
+    /// Distinct numbers of days of the lead times of all the events
+    pub fn lead_days(&self) -> Vec<u32> {
+        let mut days: Vec<u32> = self
+            .events
+            .iter()
+            .flat_map(|event| event.lead_times.iter().map(LeadTime::days))
+            .collect();
+        days.sort();
+        days.dedup();
+
+        days
+    }
+
+    /// Events to announce `days` days before the evening at which the Hijri date starts
+    pub fn announced(&self, month: u8, day: u8, days: u32) -> impl Iterator<Item = &IslamicEvent> {
+        self.events.iter().filter(move |event| {
+            event.month == month
+                && event.day == day
+                && event
+                    .lead_times
+                    .iter()
+                    .any(|lead_time| lead_time.days() == days)
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::i18n::translation_key::TranslationKey;
+
+const MUHARRAM: u8 = 1;
+const SHABAN: u8 = 8;
+const RAMADAN: u8 = 9;
+const SHAWWAL: u8 = 10;
+const DHU_AL_HIJJAH: u8 = 12;
+
+/// Days of the month called the white days, on which fasting is recommended
+pub const WHITE_DAYS: [u8; 3] = [13, 14, 15];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FastingStatus {
+    Obligatory,
+    Recommended,
+    /// Neither recommended nor disliked, which is the case for most of the days
+    Permissible,
+    Disliked,
+    Forbidden,
+}
+
+impl FastingStatus {
+    pub fn translation_key(&self) -> TranslationKey {
+        match self {
+            FastingStatus::Obligatory => TranslationKey::FastingObligatory,
+            FastingStatus::Recommended => TranslationKey::FastingRecommended,
+            FastingStatus::Permissible => TranslationKey::FastingPermissible,
+            FastingStatus::Disliked => TranslationKey::FastingDisliked,
+            FastingStatus::Forbidden => TranslationKey::FastingForbidden,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FastingReason {
+    Ramadan,
+    EidAlFitr,
+    EidAlAdha,
+    /// The three days after Eid al-Adha
+    Tashreeq,
+    /// The 30th of Sha'ban, when the start of Ramadan isn't known yet
+    DayOfDoubt,
+    Arafah,
+    Tasua,
+    Ashura,
+    /// The first eight days of Dhul-Hijjah, the ninth being the Day of Arafah
+    DhulHijjah,
+    WhiteDays,
+    Shawwal,
+    MondayThursday,
+    Muharram,
+    /// Friday, which is disliked only when fasted on its own. The ruling covers a single day, so
+    /// every Friday is marked and the text tells that fasting the day before or after is enough.
+    Friday,
+}
+
+impl FastingReason {
+    pub fn translation_key(&self) -> TranslationKey {
+        match self {
+            FastingReason::Ramadan => TranslationKey::FastingReasonRamadan,
+            FastingReason::EidAlFitr => TranslationKey::FastingReasonEidAlFitr,
+            FastingReason::EidAlAdha => TranslationKey::FastingReasonEidAlAdha,
+            FastingReason::Tashreeq => TranslationKey::FastingReasonTashreeq,
+            FastingReason::DayOfDoubt => TranslationKey::FastingReasonDayOfDoubt,
+            FastingReason::Arafah => TranslationKey::FastingReasonArafah,
+            FastingReason::Tasua => TranslationKey::FastingReasonTasua,
+            FastingReason::Ashura => TranslationKey::FastingReasonAshura,
+            FastingReason::DhulHijjah => TranslationKey::FastingReasonDhulHijjah,
+            FastingReason::WhiteDays => TranslationKey::FastingReasonWhiteDays,
+            FastingReason::Shawwal => TranslationKey::FastingReasonShawwal,
+            FastingReason::MondayThursday => TranslationKey::FastingReasonMondayThursday,
+            FastingReason::Muharram => TranslationKey::FastingReasonMuharram,
+            FastingReason::Friday => TranslationKey::FastingReasonFriday,
+        }
+    }
+}
+
+/// Whether fasting a day is allowed or recommended, and why
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastingRuling {
+    pub status: FastingStatus,
+    /// Missing for the permissible days
+    pub reason: Option<FastingReason>,
+}
+
+impl FastingRuling {
+    /// Ruling on fasting the Gregorian `date`, which is the `day` of the Hijri `month`. The
+    /// forbidden days come first, then Ramadan, and the strongest of the other reasons decides.
+    pub fn of(date: NaiveDate, month: u8, day: u8) -> Self {
+        let (status, reason) = match (month, day, date.weekday()) {
+            (SHAWWAL, 1, _) => (FastingStatus::Forbidden, FastingReason::EidAlFitr),
+            (DHU_AL_HIJJAH, 10, _) => (FastingStatus::Forbidden, FastingReason::EidAlAdha),
+            (DHU_AL_HIJJAH, 11..=13, _) => (FastingStatus::Forbidden, FastingReason::Tashreeq),
+            (RAMADAN, _, _) => (FastingStatus::Obligatory, FastingReason::Ramadan),
+            (SHABAN, 30, _) => (FastingStatus::Disliked, FastingReason::DayOfDoubt),
+            (DHU_AL_HIJJAH, 9, _) => (FastingStatus::Recommended, FastingReason::Arafah),
+            (MUHARRAM, 9, _) => (FastingStatus::Recommended, FastingReason::Tasua),
+            (MUHARRAM, 10, _) => (FastingStatus::Recommended, FastingReason::Ashura),
+            (DHU_AL_HIJJAH, 1..=8, _) => (FastingStatus::Recommended, FastingReason::DhulHijjah),
+            (_, day, _) if WHITE_DAYS.contains(&day) => {
+                (FastingStatus::Recommended, FastingReason::WhiteDays)
+            }
+            (SHAWWAL, _, _) => (FastingStatus::Recommended, FastingReason::Shawwal),
+            (_, _, Weekday::Mon | Weekday::Thu) => {
+                (FastingStatus::Recommended, FastingReason::MondayThursday)
+            }
+            (MUHARRAM, _, _) => (FastingStatus::Recommended, FastingReason::Muharram),
+            (_, _, Weekday::Fri) => (FastingStatus::Disliked, FastingReason::Friday),
+            _ => {
+                return Self {
+                    status: FastingStatus::Permissible,
+                    reason: None,
+                };
+            }
+        };
+
+        Self {
+            status,
+            reason: Some(reason),
+        }
+    }
+}
+
+/// White days of the Hijri `month` on which fasting is recommended, given the Gregorian date of
+/// its 13th day. The 13th of Dhul-Hijjah is a day of Tashreeq, and the white days of Ramadan
+/// are left out as well, since they are already obligatory.
+pub fn white_days(month: u8, thirteenth: NaiveDate) -> Vec<u8> {
+    WHITE_DAYS
+        .into_iter()
+        .filter(|day| {
+            let date = thirteenth + chrono::Duration::days((*day - WHITE_DAYS[0]) as i64);
+            FastingRuling::of(date, month, *day).status == FastingStatus::Recommended
+        })
+        .collect()
+}
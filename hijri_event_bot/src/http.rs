@@ -0,0 +1,277 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+use serde::de::DeserializeOwned;
+
+use crate::error::AppErrorKind;
+
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    /// Timeout of a single request, including reading the response body
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    /// How many times a request is repeated after a transient failure
+    pub max_retries: u32,
+    pub retry_base_delay: Duration,
+    pub retry_max_delay: Duration,
+    /// Consecutive transient failures after which the requests are stopped
+    pub failure_threshold: u32,
+    /// How long the requests are stopped for, before a single one is let through again
+    pub open_circuit_duration: Duration,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            max_retries: 3,
+            retry_base_delay: Duration::from_millis(500),
+            retry_max_delay: Duration::from_secs(8),
+            failure_threshold: 5,
+            open_circuit_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// Reads the `HIJRI_API_*` environment variables, keeping the defaults for the missing ones
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let read_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<u64>().ok())
+        };
+
+        Self {
+            timeout: read_u64("HIJRI_API_TIMEOUT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.timeout),
+            connect_timeout: read_u64("HIJRI_API_CONNECT_TIMEOUT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.connect_timeout),
+            max_retries: read_u64("HIJRI_API_MAX_RETRIES")
+                .map(|value| value as u32)
+                .unwrap_or(default.max_retries),
+            failure_threshold: read_u64("HIJRI_API_FAILURE_THRESHOLD")
+                .map(|value| value as u32)
+                .unwrap_or(default.failure_threshold),
+            open_circuit_duration: read_u64("HIJRI_API_OPEN_CIRCUIT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.open_circuit_duration),
+            ..default
+        }
+    }
+}
+
+enum CircuitState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen,
+}
+
+/// Stops the requests after too many consecutive failures, so that the API
+/// is not hammered while it is down
+struct CircuitBreaker {
+    state: Mutex<CircuitState>,
+    failure_threshold: u32,
+    open_duration: Duration,
+}
+
+impl CircuitBreaker {
+    fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            state: Mutex::new(CircuitState::Closed { failures: 0 }),
+            failure_threshold,
+            open_duration,
+        }
+    }
+
+    fn allows_request(&self) -> bool {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match *state {
+            CircuitState::Closed { .. } => true,
+            CircuitState::Open { until } if Instant::now() >= until => {
+                log::info!("Letting a trial request through the open circuit");
+                *state = CircuitState::HalfOpen;
+                true
+            }
+            CircuitState::Open { .. } | CircuitState::HalfOpen => false,
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if !matches!(*state, CircuitState::Closed { failures: 0 }) {
+            log::info!("Circuit closed, the API is reachable again");
+        }
+
+        *state = CircuitState::Closed { failures: 0 };
+    }
+
+    fn record_failure(&self) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let failures = match *state {
+            CircuitState::Closed { failures } => failures + 1,
+            CircuitState::Open { .. } | CircuitState::HalfOpen => self.failure_threshold,
+        };
+
+        if failures >= self.failure_threshold {
+            log::warn!(
+                "Circuit opened after {} failures, stopping the requests for {:?}",
+                failures,
+                self.open_duration
+            );
+            *state = CircuitState::Open {
+                until: Instant::now() + self.open_duration,
+            };
+        } else {
+            *state = CircuitState::Closed { failures };
+        }
+    }
+}
+
+pub struct HttpClient {
+    client: reqwest::Client,
+    config: HttpClientConfig,
+    circuit_breaker: CircuitBreaker,
+}
+
+impl HttpClient {
+    pub fn new(config: HttpClientConfig) -> Result<Self, AppErrorKind> {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .build()
+            .map_err(|err| {
+                log::error!("Failed to build the HTTP client: {}", err);
+                AppErrorKind::ApiUnavailable
+            })?;
+
+        Ok(Self {
+            client,
+            circuit_breaker: CircuitBreaker::new(
+                config.failure_threshold,
+                config.open_circuit_duration,
+            ),
+            config,
+        })
+    }
+
+    /// Exponential backoff with full jitter
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .config
+            .retry_base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.config.retry_max_delay);
+
+        Duration::from_millis(rand::thread_rng().gen_range(0..=delay.as_millis() as u64))
+    }
+
+    async fn try_get_json<T>(&self, url: &str) -> Result<T, AppErrorKind>
+    where
+        T: DeserializeOwned,
+    {
+        let response = self.client.get(url).send().await.map_err(|err| {
+            log::error!("Request to {} failed: {}", url, err);
+
+            if err.is_timeout() {
+                AppErrorKind::ApiTimeout
+            } else {
+                AppErrorKind::ApiUnavailable
+            }
+        })?;
+
+        let status = response.status();
+
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            log::error!("Request to {} failed with status: {}", url, status);
+            return Err(AppErrorKind::ApiUnavailable);
+        }
+
+        if !status.is_success() {
+            log::error!("Request to {} was rejected with status: {}", url, status);
+            return Err(AppErrorKind::ApiClientError);
+        }
+
+        let body = response.bytes().await.map_err(|err| {
+            log::error!("Failed to read the response from {}: {}", url, err);
+
+            if err.is_timeout() {
+                AppErrorKind::ApiTimeout
+            } else {
+                AppErrorKind::ApiUnavailable
+            }
+        })?;
+
+        serde_json::from_slice::<T>(&body).map_err(|err| {
+            log::error!("Unexpected response format from {}: {}", url, err);
+            AppErrorKind::ApiInvalidResponse
+        })
+    }
+
+    pub async fn get_json<T>(&self, url: &str) -> Result<T, AppErrorKind>
+    where
+        T: DeserializeOwned,
+    {
+        if !self.circuit_breaker.allows_request() {
+            log::warn!("Circuit is open, skipping the request to {}", url);
+            return Err(AppErrorKind::ApiCircuitOpen);
+        }
+
+        let mut attempt = 0;
+
+        loop {
+            match self.try_get_json(url).await {
+                Ok(response) => {
+                    self.circuit_breaker.record_success();
+                    return Ok(response);
+                }
+                Err(err) if err.is_transient() => {
+                    self.circuit_breaker.record_failure();
+
+                    if attempt >= self.config.max_retries {
+                        return Err(err);
+                    }
+
+                    let delay = self.retry_delay(attempt);
+                    attempt += 1;
+                    log::warn!(
+                        "Retrying the request to {} in {:?} (attempt {} of {})",
+                        url,
+                        delay,
+                        attempt,
+                        self.config.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+
+                    if !self.circuit_breaker.allows_request() {
+                        return Err(err);
+                    }
+                }
+                Err(err) => {
+                    // The API answered, so it's reachable even though it rejected the request
+                    self.circuit_breaker.record_success();
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
@@ -9,6 +9,14 @@ pub enum Language {
     Ba,
 }
 
+impl Language {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Ba => "ba",
+        }
+    }
+}
+
 #[derive(Deserialize, Clone)]
 struct Messages {
     messages: HashMap<String, String>,
@@ -37,6 +45,10 @@ impl I18n {
         })
     }
 
+    pub fn language(&self) -> &Language {
+        &self.current_language
+    }
+
     pub fn t(&self, key: &TranslationKey) -> String {
         let translations = self.translations.get(&self.current_language);
         let key: &str = From::from(key);
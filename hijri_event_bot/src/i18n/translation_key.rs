@@ -2,13 +2,137 @@
 pub enum TranslationKey {
     Help,
     CurrentHijriDate,
+    ConvertedDate,
     WelcomeMessage,
+    Stopped,
     WhiteDaysNotification,
+    WhiteDaysNotificationShortened,
+    RamadanDay,
+    RamadanDayTimes,
+    RamadanSuhoor,
+    RamadanIftar,
+    RamadanOddNight,
+    RamadanSadaqatAlFitr,
+    Fasting,
+    FastingToday,
+    FastingTomorrow,
+    FastingObligatory,
+    FastingRecommended,
+    FastingPermissible,
+    FastingDisliked,
+    FastingForbidden,
+    FastingReasonRamadan,
+    FastingReasonEidAlFitr,
+    FastingReasonEidAlAdha,
+    FastingReasonTashreeq,
+    FastingReasonDayOfDoubt,
+    FastingReasonArafah,
+    FastingReasonTasua,
+    FastingReasonAshura,
+    FastingReasonDhulHijjah,
+    FastingReasonWhiteDays,
+    FastingReasonShawwal,
+    FastingReasonMondayThursday,
+    FastingReasonMuharram,
+    FastingReasonFriday,
+    MonthStartSaved,
+    MonthStartAnnouncement,
+    CalendarHeader,
+    CalendarWeekdays,
+    CalendarLegend,
+    CalendarEvent,
+    Settings,
+    NotificationWhiteDays,
+    NotificationEvents,
+    NotificationRamadan,
+    NotificationSunnahFasts,
+    NotificationPrayerReminders,
+    NotificationMonthStart,
+    Events,
+    EventsItem,
+    EventsItemNight,
+    EventCountdownOngoing,
+    EventCountdownTonight,
+    EventCountdownToday,
+    EventCountdownTomorrow,
+    EventCountdownDay,
+    EventCountdownDays,
+    TimezoneCurrent,
+    TimezoneSaved,
+    TimezoneChoose,
+    DeliveryTimeEvening,
+    DeliveryTimeCurrent,
+    DeliveryTimeSaved,
+    DeliveryTimeReset,
+    LocationPrompt,
+    LocationButton,
+    LocationSaved,
+    LocationRemoved,
+    CurrentHijriDateAfterMaghrib,
+    NextHijriDateAtMaghrib,
+    EventReminderEvening,
+    EventReminderNight,
+    EventReminderDaysBefore,
+    EventReminderNightDaysBefore,
+    PrayerTimes,
+    PrayerTimesSettingsSaved,
+    PrayerFajr,
+    PrayerSunrise,
+    PrayerDhuhr,
+    PrayerAsr,
+    PrayerMaghrib,
+    PrayerIsha,
+    AsrMethodStandard,
+    AsrMethodHanafi,
+    PrayerReminders,
+    PrayerRemindersEmpty,
+    PrayerRemindersRemoved,
+    PrayerReminderSaved,
+    PrayerReminderItemAt,
+    PrayerReminderItemBefore,
+    PrayerReminderAt,
+    PrayerReminderBefore,
+    SunnahFasts,
+    SunnahFastsSaved,
+    SunnahFastsRemoved,
+    SunnahFastMondayThursday,
+    SunnahFastAshura,
+    SunnahFastArafah,
+    SunnahFastDhulHijjah,
+    SunnahFastShawwal,
+    SunnahFastReminderMonday,
+    SunnahFastReminderThursday,
+    SunnahFastReminderTasua,
+    SunnahFastReminderAshura,
+    SunnahFastReminderArafah,
+    SunnahFastReminderDhulHijjah,
+    SunnahFastReminderShawwal,
+    ShawwalFasts,
+    ShawwalFastsCompleted,
 
     // Error keys
     ErrorGeneral,
     ErrorScheduleWhiteDaysMessage,
     ErrorCurrentDate,
+    ErrorApiUnavailable,
+    ErrorApiInvalidResponse,
+    ErrorNotAuthorized,
+    ErrorMonthStartUsage,
+    ErrorConvertUsage,
+    ErrorConvertInvalidDate,
+    ErrorCalendarOutOfRange,
+    ErrorEventNotFound,
+    ErrorTimezoneNotFound,
+    ErrorTimezoneTooMany,
+    ErrorDeliveryTimeInvalid,
+    ErrorLocationInvalid,
+    ErrorPrayerTimesNoLocation,
+    ErrorPrayerTimesUnavailable,
+    ErrorPrayerTimesUsage,
+    ErrorPrayerReminderUsage,
+    ErrorSunnahFastUsage,
+    ErrorFastingUsage,
+    ErrorShawwalFastsOutsideShawwal,
 
     // Months
     MonthMuharram,
@@ -31,13 +155,139 @@ impl From<&TranslationKey> for &str {
         match value {
             TranslationKey::Help => "help",
             TranslationKey::CurrentHijriDate => "current_hijri_date",
+            TranslationKey::ConvertedDate => "converted_date",
             TranslationKey::WelcomeMessage => "welcome_message",
+            TranslationKey::Stopped => "stopped",
             TranslationKey::WhiteDaysNotification => "white_days_notification",
+            TranslationKey::WhiteDaysNotificationShortened => "white_days_notification_shortened",
+            TranslationKey::RamadanDay => "ramadan_day",
+            TranslationKey::RamadanDayTimes => "ramadan_day_times",
+            TranslationKey::RamadanSuhoor => "ramadan_suhoor",
+            TranslationKey::RamadanIftar => "ramadan_iftar",
+            TranslationKey::RamadanOddNight => "ramadan_odd_night",
+            TranslationKey::RamadanSadaqatAlFitr => "ramadan_sadaqat_al_fitr",
+            TranslationKey::Fasting => "fasting",
+            TranslationKey::FastingToday => "fasting_today",
+            TranslationKey::FastingTomorrow => "fasting_tomorrow",
+            TranslationKey::FastingObligatory => "fasting_obligatory",
+            TranslationKey::FastingRecommended => "fasting_recommended",
+            TranslationKey::FastingPermissible => "fasting_permissible",
+            TranslationKey::FastingDisliked => "fasting_disliked",
+            TranslationKey::FastingForbidden => "fasting_forbidden",
+            TranslationKey::FastingReasonRamadan => "fasting_reason_ramadan",
+            TranslationKey::FastingReasonEidAlFitr => "fasting_reason_eid_al_fitr",
+            TranslationKey::FastingReasonEidAlAdha => "fasting_reason_eid_al_adha",
+            TranslationKey::FastingReasonTashreeq => "fasting_reason_tashreeq",
+            TranslationKey::FastingReasonDayOfDoubt => "fasting_reason_day_of_doubt",
+            TranslationKey::FastingReasonArafah => "fasting_reason_arafah",
+            TranslationKey::FastingReasonTasua => "fasting_reason_tasua",
+            TranslationKey::FastingReasonAshura => "fasting_reason_ashura",
+            TranslationKey::FastingReasonDhulHijjah => "fasting_reason_dhul_hijjah",
+            TranslationKey::FastingReasonWhiteDays => "fasting_reason_white_days",
+            TranslationKey::FastingReasonShawwal => "fasting_reason_shawwal",
+            TranslationKey::FastingReasonMondayThursday => "fasting_reason_monday_thursday",
+            TranslationKey::FastingReasonMuharram => "fasting_reason_muharram",
+            TranslationKey::FastingReasonFriday => "fasting_reason_friday",
+            TranslationKey::MonthStartSaved => "month_start_saved",
+            TranslationKey::MonthStartAnnouncement => "month_start_announcement",
+            TranslationKey::CalendarHeader => "calendar_header",
+            TranslationKey::CalendarWeekdays => "calendar_weekdays",
+            TranslationKey::CalendarLegend => "calendar_legend",
+            TranslationKey::CalendarEvent => "calendar_event",
+            TranslationKey::Settings => "settings",
+            TranslationKey::NotificationWhiteDays => "notification_white_days",
+            TranslationKey::NotificationEvents => "notification_events",
+            TranslationKey::NotificationRamadan => "notification_ramadan",
+            TranslationKey::NotificationSunnahFasts => "notification_sunnah_fasts",
+            TranslationKey::NotificationPrayerReminders => "notification_prayer_reminders",
+            TranslationKey::NotificationMonthStart => "notification_month_start",
+            TranslationKey::Events => "events",
+            TranslationKey::EventsItem => "events_item",
+            TranslationKey::EventsItemNight => "events_item_night",
+            TranslationKey::EventCountdownOngoing => "event_countdown_ongoing",
+            TranslationKey::EventCountdownTonight => "event_countdown_tonight",
+            TranslationKey::EventCountdownToday => "event_countdown_today",
+            TranslationKey::EventCountdownTomorrow => "event_countdown_tomorrow",
+            TranslationKey::EventCountdownDay => "event_countdown_day",
+            TranslationKey::EventCountdownDays => "event_countdown_days",
+            TranslationKey::TimezoneCurrent => "timezone_current",
+            TranslationKey::TimezoneSaved => "timezone_saved",
+            TranslationKey::TimezoneChoose => "timezone_choose",
+            TranslationKey::DeliveryTimeEvening => "delivery_time_evening",
+            TranslationKey::DeliveryTimeCurrent => "delivery_time_current",
+            TranslationKey::DeliveryTimeSaved => "delivery_time_saved",
+            TranslationKey::DeliveryTimeReset => "delivery_time_reset",
+            TranslationKey::LocationPrompt => "location_prompt",
+            TranslationKey::LocationButton => "location_button",
+            TranslationKey::LocationSaved => "location_saved",
+            TranslationKey::LocationRemoved => "location_removed",
+            TranslationKey::CurrentHijriDateAfterMaghrib => "current_hijri_date_after_maghrib",
+            TranslationKey::NextHijriDateAtMaghrib => "next_hijri_date_at_maghrib",
+            TranslationKey::EventReminderEvening => "event_reminder_evening",
+            TranslationKey::EventReminderNight => "event_reminder_night",
+            TranslationKey::EventReminderDaysBefore => "event_reminder_days_before",
+            TranslationKey::EventReminderNightDaysBefore => "event_reminder_night_days_before",
+            TranslationKey::PrayerTimes => "prayer_times",
+            TranslationKey::PrayerTimesSettingsSaved => "prayer_times_settings_saved",
+            TranslationKey::PrayerFajr => "prayer_fajr",
+            TranslationKey::PrayerSunrise => "prayer_sunrise",
+            TranslationKey::PrayerDhuhr => "prayer_dhuhr",
+            TranslationKey::PrayerAsr => "prayer_asr",
+            TranslationKey::PrayerMaghrib => "prayer_maghrib",
+            TranslationKey::PrayerIsha => "prayer_isha",
+            TranslationKey::AsrMethodStandard => "asr_method_standard",
+            TranslationKey::AsrMethodHanafi => "asr_method_hanafi",
+            TranslationKey::PrayerReminders => "prayer_reminders",
+            TranslationKey::PrayerRemindersEmpty => "prayer_reminders_empty",
+            TranslationKey::PrayerRemindersRemoved => "prayer_reminders_removed",
+            TranslationKey::PrayerReminderSaved => "prayer_reminder_saved",
+            TranslationKey::PrayerReminderItemAt => "prayer_reminder_item_at",
+            TranslationKey::PrayerReminderItemBefore => "prayer_reminder_item_before",
+            TranslationKey::PrayerReminderAt => "prayer_reminder_at",
+            TranslationKey::PrayerReminderBefore => "prayer_reminder_before",
+            TranslationKey::SunnahFasts => "sunnah_fasts",
+            TranslationKey::SunnahFastsSaved => "sunnah_fasts_saved",
+            TranslationKey::SunnahFastsRemoved => "sunnah_fasts_removed",
+            TranslationKey::SunnahFastMondayThursday => "sunnah_fast_monday_thursday",
+            TranslationKey::SunnahFastAshura => "sunnah_fast_ashura",
+            TranslationKey::SunnahFastArafah => "sunnah_fast_arafah",
+            TranslationKey::SunnahFastDhulHijjah => "sunnah_fast_dhul_hijjah",
+            TranslationKey::SunnahFastShawwal => "sunnah_fast_shawwal",
+            TranslationKey::SunnahFastReminderMonday => "sunnah_fast_reminder_monday",
+            TranslationKey::SunnahFastReminderThursday => "sunnah_fast_reminder_thursday",
+            TranslationKey::SunnahFastReminderTasua => "sunnah_fast_reminder_tasua",
+            TranslationKey::SunnahFastReminderAshura => "sunnah_fast_reminder_ashura",
+            TranslationKey::SunnahFastReminderArafah => "sunnah_fast_reminder_arafah",
+            TranslationKey::SunnahFastReminderDhulHijjah => "sunnah_fast_reminder_dhul_hijjah",
+            TranslationKey::SunnahFastReminderShawwal => "sunnah_fast_reminder_shawwal",
+            TranslationKey::ShawwalFasts => "shawwal_fasts",
+            TranslationKey::ShawwalFastsCompleted => "shawwal_fasts_completed",
 
             // Error keys
             TranslationKey::ErrorGeneral => "error_general",
             TranslationKey::ErrorScheduleWhiteDaysMessage => "error_schedule_white_days_message",
             TranslationKey::ErrorCurrentDate => "error_current_date",
+            TranslationKey::ErrorApiUnavailable => "error_api_unavailable",
+            TranslationKey::ErrorApiInvalidResponse => "error_api_invalid_response",
+            TranslationKey::ErrorNotAuthorized => "error_not_authorized",
+            TranslationKey::ErrorMonthStartUsage => "error_month_start_usage",
+            TranslationKey::ErrorConvertUsage => "error_convert_usage",
+            TranslationKey::ErrorConvertInvalidDate => "error_convert_invalid_date",
+            TranslationKey::ErrorCalendarOutOfRange => "error_calendar_out_of_range",
+            TranslationKey::ErrorEventNotFound => "error_event_not_found",
+            TranslationKey::ErrorTimezoneNotFound => "error_timezone_not_found",
+            TranslationKey::ErrorTimezoneTooMany => "error_timezone_too_many",
+            TranslationKey::ErrorDeliveryTimeInvalid => "error_delivery_time_invalid",
+            TranslationKey::ErrorLocationInvalid => "error_location_invalid",
+            TranslationKey::ErrorPrayerTimesNoLocation => "error_prayer_times_no_location",
+            TranslationKey::ErrorPrayerTimesUnavailable => "error_prayer_times_unavailable",
+            TranslationKey::ErrorPrayerTimesUsage => "error_prayer_times_usage",
+            TranslationKey::ErrorPrayerReminderUsage => "error_prayer_reminder_usage",
+            TranslationKey::ErrorSunnahFastUsage => "error_sunnah_fast_usage",
+            TranslationKey::ErrorFastingUsage => "error_fasting_usage",
+            TranslationKey::ErrorShawwalFastsOutsideShawwal => {
+                "error_shawwal_fasts_outside_shawwal"
+            }
 
             // Months
             TranslationKey::MonthMuharram => "month_muharram",
@@ -0,0 +1,114 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+
+use crate::{
+    api::CurrentDateResponse, error::AppErrorKind, location::Location, provider::HijriDateProvider,
+    solar,
+};
+
+/// Local hour used as the evening for the users without a location
+pub const DEFAULT_EVENING_HOUR: u32 = 18;
+
+/// The Islamic day starts at Maghrib, so the Hijri date of a Gregorian day lasts from the
+/// sunset of the previous day until the sunset of that day
+pub struct IslamicDay {
+    /// Hijri date of the Gregorian day, which lasts until the sunset
+    pub daytime: CurrentDateResponse,
+    /// Today's sunset and the Hijri date starting with it, when the user has a location
+    pub evening: Option<(DateTime<chrono_tz::Tz>, CurrentDateResponse)>,
+    now: DateTime<chrono_tz::Tz>,
+}
+
+impl IslamicDay {
+    pub async fn current(
+        provider: &dyn HijriDateProvider,
+        timezone: chrono_tz::Tz,
+        location: Option<Location>,
+    ) -> Result<Self, AppErrorKind> {
+        let now = Utc::now().with_timezone(&timezone);
+        let daytime = provider.get_current_hijri_date_in(timezone).await?;
+
+        let sunset = location
+            .and_then(|location| solar::sunset(now.date_naive(), location))
+            .map(|sunset| sunset.with_timezone(&timezone));
+        let evening = match (sunset, now.date_naive().succ_opt()) {
+            (Some(sunset), Some(tomorrow)) => {
+                Some((sunset, provider.get_hijri_date(tomorrow).await?))
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            daytime,
+            evening,
+            now,
+        })
+    }
+
+    pub fn is_after_sunset(&self) -> bool {
+        self.evening
+            .as_ref()
+            .is_some_and(|(sunset, _)| self.now >= *sunset)
+    }
+
+    /// Hijri date at this moment
+    pub fn date(&self) -> &CurrentDateResponse {
+        match &self.evening {
+            Some((_, evening)) if self.is_after_sunset() => evening,
+            _ => &self.daytime,
+        }
+    }
+}
+
+/// Moment of the evening of the local day, at which the evening reminders are sent. It is the
+/// sunset for the users with a location, or 18:00 otherwise.
+pub fn evening_of(
+    date: NaiveDate,
+    timezone: chrono_tz::Tz,
+    location: Option<Location>,
+) -> Option<DateTime<chrono_tz::Tz>> {
+    if let Some(sunset) = location.and_then(|location| solar::sunset(date, location)) {
+        return Some(sunset.with_timezone(&timezone));
+    }
+
+    timezone
+        .from_local_datetime(&date.and_hms_opt(DEFAULT_EVENING_HOUR, 0, 0)?)
+        .earliest()
+}
+
+/// Moment of the local day at which the reminders for the next day are sent: the delivery time
+/// the user has chosen, or else the evening
+pub fn delivery_of(
+    date: NaiveDate,
+    timezone: chrono_tz::Tz,
+    location: Option<Location>,
+    delivery_time: Option<NaiveTime>,
+) -> Option<DateTime<chrono_tz::Tz>> {
+    match delivery_time {
+        Some(delivery_time) => timezone
+            .from_local_datetime(&date.and_time(delivery_time))
+            .earliest(),
+        None => evening_of(date, timezone, location),
+    }
+}
+
+/// Whether this evening's reminder falls within the check window starting now
+pub fn is_evening_due(
+    timezone: chrono_tz::Tz,
+    location: Option<Location>,
+    window: chrono::Duration,
+) -> bool {
+    is_delivery_due(timezone, location, None, window)
+}
+
+/// Whether today's reminder for the next day falls within the check window starting now
+pub fn is_delivery_due(
+    timezone: chrono_tz::Tz,
+    location: Option<Location>,
+    delivery_time: Option<NaiveTime>,
+    window: chrono::Duration,
+) -> bool {
+    let now = Utc::now().with_timezone(&timezone);
+
+    delivery_of(now.date_naive(), timezone, location, delivery_time)
+        .is_some_and(|delivery| delivery <= now && now < delivery + window)
+}
@@ -1,14 +1,44 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(sqlx::Type, Serialize, Deserialize, Debug)]
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JobExtensionType {
     WhiteDaysMessage = 1,
+    EventReminder = 2,
+    PrayerReminder = 3,
+    MondayThursdayFast = 4,
+    AshuraFast = 5,
+    ArafahFast = 6,
+    DhulHijjahFast = 7,
+    ShawwalFast = 8,
+    RamadanReminder = 9,
+}
+
+impl JobExtensionType {
+    pub const ALL: [JobExtensionType; 9] = [
+        JobExtensionType::WhiteDaysMessage,
+        JobExtensionType::EventReminder,
+        JobExtensionType::PrayerReminder,
+        JobExtensionType::MondayThursdayFast,
+        JobExtensionType::AshuraFast,
+        JobExtensionType::ArafahFast,
+        JobExtensionType::DhulHijjahFast,
+        JobExtensionType::ShawwalFast,
+        JobExtensionType::RamadanReminder,
+    ];
 }
 
 impl From<i32> for JobExtensionType {
     fn from(value: i32) -> Self {
         match value {
             1 => JobExtensionType::WhiteDaysMessage,
+            2 => JobExtensionType::EventReminder,
+            3 => JobExtensionType::PrayerReminder,
+            4 => JobExtensionType::MondayThursdayFast,
+            5 => JobExtensionType::AshuraFast,
+            6 => JobExtensionType::ArafahFast,
+            7 => JobExtensionType::DhulHijjahFast,
+            8 => JobExtensionType::ShawwalFast,
+            9 => JobExtensionType::RamadanReminder,
             _ => panic!("Unknown JobExtensionType value: {}", value),
         }
     }
@@ -17,4 +47,7 @@ impl From<i32> for JobExtensionType {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JobExtraData {
     pub extension_type: JobExtensionType,
+    /// User of the job, for the jobs of a single user
+    #[serde(default)]
+    pub chat_id: Option<i64>,
 }
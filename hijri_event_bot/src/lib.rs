@@ -0,0 +1,27 @@
+pub mod api;
+pub mod bot;
+pub mod calendar;
+pub mod command;
+pub mod convert;
+pub mod delivery_time;
+pub mod error;
+pub mod event;
+pub mod fasting;
+pub mod http;
+pub mod i18n;
+pub mod islamic_day;
+pub mod job;
+pub mod location;
+pub mod month_view;
+pub mod notification;
+pub mod prayer_reminder;
+pub mod prayer_times;
+pub mod provider;
+pub mod ramadan;
+pub mod scheduler;
+pub mod solar;
+pub mod sunnah_fast;
+pub mod timezone;
+
+#[macro_use]
+extern crate log;
@@ -0,0 +1,84 @@
+use sqlx::{Pool, Postgres, types::Uuid};
+
+use crate::error::AppErrorKind;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    /// In degrees, positive to the north
+    pub latitude: f64,
+    /// In degrees, positive to the east
+    pub longitude: f64,
+}
+
+impl Location {
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then_some(
+            Self {
+                latitude,
+                longitude,
+            },
+        )
+    }
+
+    /// Parses coordinates such as `43.8563 18.4131` or `43.8563, 18.4131`
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .filter(|part| !part.is_empty());
+
+        let latitude = parts.next()?.parse().ok()?;
+        let longitude = parts.next()?.parse().ok()?;
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Location::new(latitude, longitude)
+    }
+}
+
+pub async fn get_user_location(pool: &Pool<Postgres>, chat_id: i64) -> Option<Location> {
+    let location = sqlx::query!(
+        "SELECT latitude, longitude FROM users WHERE chat_id = $1",
+        chat_id
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap_or_else(|e| {
+        log::error!("Failed to fetch the location of {}: {}", chat_id, e);
+        None
+    })?;
+
+    Location::new(location.latitude?, location.longitude?)
+}
+
+pub async fn set_user_location(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    username: Option<String>,
+    location: Option<Location>,
+) -> Result<(), AppErrorKind> {
+    sqlx::query!(
+        "
+            INSERT INTO users (id, chat_id, username, latitude, longitude)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (chat_id)
+            DO UPDATE SET latitude = EXCLUDED.latitude, longitude = EXCLUDED.longitude
+        ",
+        Uuid::new_v4(),
+        chat_id,
+        username,
+        location.map(|location| location.latitude),
+        location.map(|location| location.longitude)
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to set the location of {}: {}", chat_id, e);
+        AppErrorKind::UserLocation
+    })?;
+
+    log::info!("Location of {} set to {:?}", chat_id, location);
+
+    Ok(())
+}
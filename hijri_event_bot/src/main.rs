@@ -1,18 +1,25 @@
-use bot_core::db::migrator::MigrationProject;
+use bot_core::{db::migrator::MigrationProject, dispatcher::NotificationDispatcher};
 
-use crate::{api::HijriApi, bot::TelegramBot, i18n::instance::I18n, scheduler::Scheduler};
-
-mod api;
-mod bot;
-mod command;
-mod error;
-mod i18n;
-mod job;
-mod scheduler;
+use hijri_event_bot::{
+    api::HijriApi,
+    bot::TelegramBot,
+    calendar::{CalendarMethod, HijriCalendar},
+    convert::DateConverter,
+    event::EventCatalog,
+    http::{HttpClient, HttpClientConfig},
+    i18n::instance::I18n,
+    prayer_times::PrayerTimesConfig,
+    provider::{
+        HijriDateProvider,
+        cache::HijriDateCache,
+        chain::HijriDateProviderChain,
+        database::{DatabaseOverrideProvider, watch_overrides},
+        month_start::MonthStartProvider,
+    },
+    scheduler::Scheduler,
+};
 
 extern crate pretty_env_logger;
-#[macro_use]
-extern crate log;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -32,11 +39,109 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Migrator::run(&database_url, MigrationProject::HijriEventBot).await?;
 
     let i18n = Arc::new(I18n::new().expect("Failed to initialize i18n"));
-    let api = Arc::new(HijriApi::new(i18n.clone()));
+    // A typo in the method would quietly switch the calendar, so an unknown one stops the bot
+    let calendar_method = match std::env::var("HIJRI_CALENDAR_METHOD") {
+        Ok(value) => value
+            .parse::<CalendarMethod>()
+            .map_err(|_err| "Unknown HIJRI_CALENDAR_METHOD")?,
+        Err(_e) => CalendarMethod::UmmAlQura,
+    };
+    let http_client = HttpClient::new(HttpClientConfig::from_env())
+        .map_err(|_err| "Failed to initialize the HTTP client")?;
+    let mut api = HijriApi::new(i18n.clone(), http_client);
+
+    if let Ok(api_url) = std::env::var("HIJRI_API_URL") {
+        api = api.with_base_url(&api_url);
+    }
+
+    let api = Arc::new(api);
+    let mut calendar = HijriCalendar::new(calendar_method, i18n.clone());
+
+    if std::env::var("HIJRI_API_CROSS_CHECK").is_ok_and(|value| value == "true") {
+        calendar = calendar.with_cross_check(api.clone());
+    }
+
+    let calendar = Arc::new(calendar);
     let pool = sqlx::Pool::<sqlx::Postgres>::connect(&database_url).await?;
-    let scheduler = Scheduler::new(pool.clone(), api.clone(), i18n.clone()).await?;
 
-    let bot = TelegramBot::new(api, i18n, pool, scheduler);
+    let month_starts = Arc::new(MonthStartProvider::new(
+        pool.clone(),
+        calendar.clone(),
+        i18n.clone(),
+    ));
+    let mut provider_chain = HijriDateProviderChain::new();
+
+    for provider_name in std::env::var("HIJRI_DATE_PROVIDERS")
+        .unwrap_or("database,announcement,offline".to_string())
+        .split(',')
+    {
+        let provider: Arc<dyn HijriDateProvider> = match provider_name.trim() {
+            "database" => Arc::new(DatabaseOverrideProvider::new(pool.clone(), i18n.clone())),
+            "announcement" => month_starts.clone(),
+            "offline" => calendar.clone(),
+            "aladhan" => api.clone(),
+            name => {
+                log::warn!("Unknown Hijri date provider: {}, skipping it", name);
+                continue;
+            }
+        };
+        provider_chain = provider_chain.with_provider(provider);
+    }
+
+    if provider_chain.is_empty() {
+        log::warn!("No Hijri date providers configured, falling back to the offline calendar");
+        provider_chain = provider_chain.with_provider(calendar.clone());
+    }
+
+    let provider: Arc<dyn HijriDateProvider> = Arc::new(HijriDateCache::new(
+        Arc::new(provider_chain),
+        pool.clone(),
+        i18n.clone(),
+    ));
+    tokio::spawn(watch_overrides(pool.clone(), provider.clone()));
+    let prayer_times_config = PrayerTimesConfig::from_env();
+    let scheduler = Scheduler::new(pool.clone(), provider.clone(), i18n.clone())
+        .await?
+        .with_prayer_times_config(prayer_times_config)
+        .with_dispatcher(NotificationDispatcher::from_env());
+    let events = Arc::new(EventCatalog::new().map_err(|_err| "Failed to load the events catalog")?);
+    scheduler
+        .rehydrate_jobs(teloxide::Bot::from_env(), events.clone())
+        .await
+        .map_err(|_err| "Failed to rehydrate the persisted jobs")?;
+    scheduler
+        .schedule_white_days_message(teloxide::Bot::from_env())
+        .await
+        .map_err(|_err| "Failed to schedule the white days message")?;
+    scheduler
+        .schedule_event_reminders(teloxide::Bot::from_env(), events.clone())
+        .await
+        .map_err(|_err| "Failed to schedule the event reminders")?;
+    scheduler
+        .schedule_ramadan_reminders(teloxide::Bot::from_env())
+        .await
+        .map_err(|_err| "Failed to schedule the Ramadan reminders")?;
+
+    let catch_up_scheduler = scheduler.clone();
+    let catch_up_events = events.clone();
+    tokio::spawn(async move {
+        catch_up_scheduler
+            .catch_up(teloxide::Bot::from_env(), catch_up_events)
+            .await;
+    });
+
+    let converter = Arc::new(DateConverter::new(calendar, provider.clone()));
+
+    let bot = TelegramBot::new(
+        provider,
+        converter,
+        month_starts,
+        events,
+        i18n,
+        pool,
+        scheduler,
+    )
+    .with_prayer_times_config(prayer_times_config);
 
     bot.run().await;
 
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+use teloxide::{
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+    utils::html,
+};
+
+use crate::{
+    api::CurrentDateResponse,
+    calendar::arabic_month_name,
+    convert::DateConverter,
+    error::AppErrorKind,
+    event::{EventCatalog, IslamicEvent},
+    fasting::{WHITE_DAYS, white_days},
+    i18n::{instance::I18n, translation_key::TranslationKey},
+};
+
+/// Prefix of the callback data of the navigation buttons, followed by `{year}:{month}`
+pub const CALENDAR_CALLBACK_PREFIX: &str = "calendar:";
+
+/// A Hijri month laid out as a Monday-first grid, with the Gregorian day under each Hijri day
+pub struct MonthView {
+    pub year: i32,
+    pub month: u8,
+    start: NaiveDate,
+    days: u8,
+    events: Vec<IslamicEvent>,
+}
+
+impl MonthView {
+    pub async fn load(
+        converter: &DateConverter,
+        events: &EventCatalog,
+        year: i32,
+        month: u8,
+    ) -> Result<Self, AppErrorKind> {
+        let (start, days) = converter.month_bounds(year, month).await?;
+
+        Ok(Self {
+            year,
+            month,
+            start,
+            days,
+            events: events.in_month(month).cloned().collect(),
+        })
+    }
+
+    pub fn previous(&self) -> (i32, u8) {
+        match self.month {
+            1 => (self.year - 1, 12),
+            month => (self.year, month - 1),
+        }
+    }
+
+    pub fn next(&self) -> (i32, u8) {
+        match self.month {
+            12 => (self.year + 1, 1),
+            month => (self.year, month + 1),
+        }
+    }
+
+    fn gregorian_date(&self, day: u8) -> NaiveDate {
+        self.start + chrono::Duration::days(day as i64 - 1)
+    }
+
+    fn render_grid(&self, today: NaiveDate, i18n: &I18n) -> String {
+        let mut lines = vec![
+            i18n.t(&TranslationKey::CalendarWeekdays)
+                .split_whitespace()
+                .map(|weekday| format!("{:^4}", weekday))
+                .collect::<String>(),
+        ];
+
+        let white_days = white_days(self.month, self.gregorian_date(WHITE_DAYS[0]));
+        let offset = self.start.weekday().num_days_from_monday() as usize;
+        let cells: Vec<Option<u8>> = std::iter::repeat_n(None, offset)
+            .chain((1..=self.days).map(Some))
+            .collect();
+
+        for week in cells.chunks(7) {
+            let mut hijri_line = String::new();
+            let mut gregorian_line = String::new();
+
+            for cell in week {
+                let day = match cell {
+                    Some(day) => *day,
+                    None => {
+                        hijri_line.push_str("    ");
+                        gregorian_line.push_str("    ");
+                        continue;
+                    }
+                };
+                let date = self.gregorian_date(day);
+
+                let (left, right) = if date == today {
+                    ('[', ']')
+                } else if white_days.contains(&day) {
+                    ('(', ')')
+                } else if self
+                    .events
+                    .iter()
+                    .any(|event| event.falls_on(self.month, day))
+                {
+                    (' ', '!')
+                } else {
+                    (' ', ' ')
+                };
+
+                hijri_line.push_str(&format!("{}{:>2}{}", left, day, right));
+                gregorian_line.push_str(&format!(" {:>2} ", date.day()));
+            }
+
+            lines.push(String::new());
+            lines.push(hijri_line);
+            lines.push(gregorian_line);
+        }
+
+        lines
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders the month as an HTML message
+    pub fn render(&self, today: NaiveDate, i18n: &I18n) -> String {
+        let mut args = HashMap::new();
+        args.insert(
+            "month_name",
+            CurrentDateResponse::map_translated_month(self.month, i18n),
+        );
+        args.insert("month_ar", arabic_month_name(self.month).to_string());
+        args.insert("year", self.year.to_string());
+        args.insert("start", self.start.format("%d.%m.%Y.").to_string());
+        args.insert(
+            "end",
+            self.gregorian_date(self.days)
+                .format("%d.%m.%Y.")
+                .to_string(),
+        );
+
+        let mut message = format!(
+            "<b>{}</b>\n<pre>{}</pre>\n{}",
+            html::escape(&i18n.t_with_args(&TranslationKey::CalendarHeader, args)),
+            html::escape(&self.render_grid(today, i18n)),
+            html::escape(&i18n.t(&TranslationKey::CalendarLegend))
+        );
+
+        for event in self.events.iter().filter(|event| event.day <= self.days) {
+            let mut args = HashMap::new();
+            args.insert("day", event.day.to_string());
+            args.insert(
+                "date",
+                self.gregorian_date(event.day)
+                    .format("%d.%m.%Y.")
+                    .to_string(),
+            );
+            args.insert(
+                "month_name",
+                CurrentDateResponse::map_translated_month(self.month, i18n),
+            );
+            args.insert("name", event.name(i18n));
+
+            message.push('\n');
+            message.push_str(&html::escape(
+                &i18n.t_with_args(&TranslationKey::CalendarEvent, args),
+            ));
+        }
+
+        message
+    }
+
+    pub fn keyboard(&self, i18n: &I18n) -> InlineKeyboardMarkup {
+        let (previous_year, previous_month) = self.previous();
+        let (next_year, next_month) = self.next();
+
+        InlineKeyboardMarkup::new([[
+            InlineKeyboardButton::callback(
+                format!(
+                    "◀ {}",
+                    CurrentDateResponse::map_translated_month(previous_month, i18n)
+                ),
+                format!(
+                    "{}{}:{}",
+                    CALENDAR_CALLBACK_PREFIX, previous_year, previous_month
+                ),
+            ),
+            InlineKeyboardButton::callback(
+                format!(
+                    "{} ▶",
+                    CurrentDateResponse::map_translated_month(next_month, i18n)
+                ),
+                format!("{}{}:{}", CALENDAR_CALLBACK_PREFIX, next_year, next_month),
+            ),
+        ]])
+    }
+
+    /// Parses the callback data of the navigation buttons into the year and the month
+    pub fn parse_callback_data(data: &str) -> Option<(i32, u8)> {
+        let (year, month) = data
+            .strip_prefix(CALENDAR_CALLBACK_PREFIX)?
+            .split_once(':')?;
+        let month = month
+            .parse()
+            .ok()
+            .filter(|month| (1..=12).contains(month))?;
+
+        Some((year.parse().ok()?, month))
+    }
+}
@@ -0,0 +1,163 @@
+use std::str::FromStr;
+
+use sqlx::{Pool, Postgres};
+
+use crate::{error::AppErrorKind, i18n::translation_key::TranslationKey};
+
+/// Kinds of the notifications the users can turn off with `/settings`. All of them are on
+/// until the user turns them off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationCategory {
+    WhiteDays,
+    Events,
+    Ramadan,
+    SunnahFasts,
+    PrayerReminders,
+    MonthStart,
+}
+
+impl NotificationCategory {
+    pub const ALL: [NotificationCategory; 6] = [
+        NotificationCategory::WhiteDays,
+        NotificationCategory::Events,
+        NotificationCategory::Ramadan,
+        NotificationCategory::SunnahFasts,
+        NotificationCategory::PrayerReminders,
+        NotificationCategory::MonthStart,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationCategory::WhiteDays => "white_days",
+            NotificationCategory::Events => "events",
+            NotificationCategory::Ramadan => "ramadan",
+            NotificationCategory::SunnahFasts => "sunnah_fasts",
+            NotificationCategory::PrayerReminders => "prayer_reminders",
+            NotificationCategory::MonthStart => "month_start",
+        }
+    }
+
+    pub fn translation_key(&self) -> TranslationKey {
+        match self {
+            NotificationCategory::WhiteDays => TranslationKey::NotificationWhiteDays,
+            NotificationCategory::Events => TranslationKey::NotificationEvents,
+            NotificationCategory::Ramadan => TranslationKey::NotificationRamadan,
+            NotificationCategory::SunnahFasts => TranslationKey::NotificationSunnahFasts,
+            NotificationCategory::PrayerReminders => TranslationKey::NotificationPrayerReminders,
+            NotificationCategory::MonthStart => TranslationKey::NotificationMonthStart,
+        }
+    }
+}
+
+impl FromStr for NotificationCategory {
+    type Err = AppErrorKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        NotificationCategory::ALL
+            .into_iter()
+            .find(|category| category.as_str() == value)
+            .ok_or(AppErrorKind::NotificationPreferences)
+    }
+}
+
+/// Categories the user has turned off
+pub async fn get_disabled_notifications(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+) -> Result<Vec<NotificationCategory>, AppErrorKind> {
+    let categories = sqlx::query_scalar!(
+        "
+            SELECT notification_preferences.category
+            FROM notification_preferences
+            JOIN users ON users.id = notification_preferences.user_id
+            WHERE users.chat_id = $1 AND NOT notification_preferences.enabled
+        ",
+        chat_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        log::error!(
+            "Failed to fetch the notification preferences of {}: {}",
+            chat_id,
+            e
+        );
+        AppErrorKind::NotificationPreferences
+    })?;
+
+    Ok(categories
+        .iter()
+        .filter_map(|category| category.parse().ok())
+        .collect())
+}
+
+/// Whether the user receives the notifications of the category. The inactive users receive
+/// none, and the notifications are sent when the preferences can't be read.
+pub async fn is_notification_enabled(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    category: NotificationCategory,
+) -> bool {
+    sqlx::query_scalar!(
+        r#"
+            SELECT users.active AND NOT EXISTS (
+                SELECT 1
+                FROM notification_preferences
+                WHERE user_id = users.id AND category = $2 AND NOT enabled
+            ) AS "enabled!"
+            FROM users
+            WHERE users.chat_id = $1
+        "#,
+        chat_id,
+        category.as_str()
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap_or_else(|e| {
+        log::error!(
+            "Failed to fetch the notification preferences of {}: {}",
+            chat_id,
+            e
+        );
+        None
+    })
+    .unwrap_or(true)
+}
+
+pub async fn set_notification_enabled(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    category: NotificationCategory,
+    enabled: bool,
+) -> Result<(), AppErrorKind> {
+    sqlx::query!(
+        "
+            INSERT INTO notification_preferences (user_id, category, enabled)
+            SELECT id, $2, $3 FROM users WHERE chat_id = $1
+            ON CONFLICT (user_id, category)
+            DO UPDATE SET enabled = EXCLUDED.enabled
+        ",
+        chat_id,
+        category.as_str(),
+        enabled
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        log::error!(
+            "Failed to set the notification preference of {}: {}",
+            chat_id,
+            e
+        );
+        AppErrorKind::NotificationPreferences
+    })?;
+
+    log::info!(
+        "Notifications {:?} of {} turned {}",
+        category,
+        chat_id,
+        if enabled { "on" } else { "off" }
+    );
+
+    Ok(())
+}
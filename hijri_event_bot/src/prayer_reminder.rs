@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    error::AppErrorKind,
+    location::Location,
+    prayer_times::{Prayer, PrayerTimes, PrayerTimesConfig},
+};
+
+/// Longest time before a prayer a reminder can be sent at
+pub const MAX_MINUTES_BEFORE: i64 = 180;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrayerReminder {
+    pub prayer: Prayer,
+    /// Minutes before the start of the prayer time, 0 to remind at its start
+    pub minutes_before: i64,
+}
+
+impl PrayerReminder {
+    pub fn new(prayer: Prayer, minutes_before: i64) -> Option<Self> {
+        (0..=MAX_MINUTES_BEFORE)
+            .contains(&minutes_before)
+            .then_some(Self {
+                prayer,
+                minutes_before,
+            })
+    }
+
+    pub fn time(&self, prayer_times: &PrayerTimes) -> DateTime<Utc> {
+        prayer_times.time(self.prayer) - chrono::Duration::minutes(self.minutes_before)
+    }
+}
+
+/// Prayer times of the local days around the moment, in which the reminders close to it fall
+fn prayer_times_around(
+    at: DateTime<Utc>,
+    location: Location,
+    timezone: chrono_tz::Tz,
+    config: &PrayerTimesConfig,
+) -> Vec<PrayerTimes> {
+    let today = at.with_timezone(&timezone).date_naive();
+
+    [today.pred_opt(), Some(today), today.succ_opt()]
+        .into_iter()
+        .flatten()
+        .filter_map(|date| PrayerTimes::calculate(date, location, config))
+        .collect()
+}
+
+/// Moment of the first reminder after the given one, if there is one within the next day
+pub fn next_reminder_time(
+    reminders: &[PrayerReminder],
+    location: Location,
+    timezone: chrono_tz::Tz,
+    config: &PrayerTimesConfig,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    prayer_times_around(after, location, timezone, config)
+        .iter()
+        .flat_map(|prayer_times| reminders.iter().map(|reminder| reminder.time(prayer_times)))
+        .filter(|time| *time > after)
+        .min()
+}
+
+/// Reminders due at the moment, along with the start of their prayer time
+pub fn due_reminders(
+    reminders: &[PrayerReminder],
+    location: Location,
+    timezone: chrono_tz::Tz,
+    config: &PrayerTimesConfig,
+    at: DateTime<Utc>,
+) -> Vec<(PrayerReminder, DateTime<Utc>)> {
+    prayer_times_around(at, location, timezone, config)
+        .iter()
+        .flat_map(|prayer_times| {
+            reminders
+                .iter()
+                .filter(|reminder| (reminder.time(prayer_times) - at).num_seconds().abs() < 60)
+                .map(|reminder| (*reminder, prayer_times.time(reminder.prayer)))
+        })
+        .collect()
+}
+
+pub async fn get_prayer_reminders(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+) -> Result<Vec<PrayerReminder>, AppErrorKind> {
+    let rows = sqlx::query!(
+        "
+            SELECT prayer_reminders.prayer, prayer_reminders.minutes_before
+            FROM prayer_reminders
+            JOIN users ON users.id = prayer_reminders.user_id
+            WHERE users.chat_id = $1
+        ",
+        chat_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to fetch the prayer reminders of {}: {}", chat_id, e);
+        AppErrorKind::PrayerReminder
+    })?;
+
+    let mut reminders: Vec<PrayerReminder> = rows
+        .into_iter()
+        .filter_map(|row| PrayerReminder::new(row.prayer.parse().ok()?, row.minutes_before as i64))
+        .collect();
+    reminders.sort_by_key(|reminder| reminder.prayer as u8);
+
+    Ok(reminders)
+}
+
+/// Adds the reminder, replacing the existing reminder of the same prayer
+pub async fn set_prayer_reminder(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    reminder: PrayerReminder,
+) -> Result<(), AppErrorKind> {
+    let result = sqlx::query!(
+        "
+            INSERT INTO prayer_reminders (user_id, prayer, minutes_before)
+            SELECT id, $2, $3 FROM users WHERE chat_id = $1
+            ON CONFLICT (user_id, prayer)
+            DO UPDATE SET minutes_before = EXCLUDED.minutes_before
+        ",
+        chat_id,
+        reminder.prayer.as_str(),
+        reminder.minutes_before as i32
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to set the prayer reminder of {}: {}", chat_id, e);
+        AppErrorKind::PrayerReminder
+    })?;
+
+    if result.rows_affected() == 0 {
+        log::error!("User {} not found for the prayer reminder", chat_id);
+        return Err(AppErrorKind::PrayerReminder);
+    }
+
+    log::info!("Prayer reminder {:?} set for {}", reminder, chat_id);
+
+    Ok(())
+}
+
+/// Removes the reminder of the prayer, or all the reminders of the user
+pub async fn remove_prayer_reminders(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    prayer: Option<Prayer>,
+) -> Result<(), AppErrorKind> {
+    sqlx::query!(
+        "
+            DELETE FROM prayer_reminders
+            USING users
+            WHERE users.id = prayer_reminders.user_id
+                AND users.chat_id = $1
+                AND ($2::VARCHAR IS NULL OR prayer_reminders.prayer = $2)
+        ",
+        chat_id,
+        prayer.map(|prayer| prayer.as_str())
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        log::error!(
+            "Failed to remove the prayer reminders of {}: {}",
+            chat_id,
+            e
+        );
+        AppErrorKind::PrayerReminder
+    })?;
+
+    log::info!("Prayer reminders {:?} of {} removed", prayer, chat_id);
+
+    Ok(())
+}
@@ -0,0 +1,405 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use sqlx::{Pool, Postgres, types::Uuid};
+
+use crate::{
+    error::AppErrorKind,
+    i18n::translation_key::TranslationKey,
+    location::Location,
+    solar::{self, SunDirection},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalculationMethod {
+    /// Muslim World League, Fajr at 18° and Isha at 17°
+    MuslimWorldLeague,
+    /// Islamic Society of North America, Fajr and Isha at 15°
+    Isna,
+    /// Umm al-Qura University of Makkah, Fajr at 18.5° and Isha 90 minutes after Maghrib
+    UmmAlQura,
+    /// Presidency of Religious Affairs of Turkey, Fajr at 18° and Isha at 17° with the
+    /// safety minutes (temkin) added to the other prayers
+    Diyanet,
+    /// Close to the vaktija published by the Islamic Community in Bosnia and Herzegovina,
+    /// Fajr at 19° and Isha at 17°
+    IzBih,
+}
+
+enum IshaRule {
+    Angle(f64),
+    MinutesAfterMaghrib(i64),
+}
+
+struct MethodParameters {
+    fajr_angle: f64,
+    isha: IshaRule,
+    /// Minutes added to each prayer, in the order of `Prayer::ALL`
+    adjustments: [i64; 6],
+}
+
+impl CalculationMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalculationMethod::MuslimWorldLeague => "mwl",
+            CalculationMethod::Isna => "isna",
+            CalculationMethod::UmmAlQura => "umm_al_qura",
+            CalculationMethod::Diyanet => "diyanet",
+            CalculationMethod::IzBih => "iz_bih",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CalculationMethod::MuslimWorldLeague => "MWL",
+            CalculationMethod::Isna => "ISNA",
+            CalculationMethod::UmmAlQura => "Umm al-Qura",
+            CalculationMethod::Diyanet => "Diyanet",
+            CalculationMethod::IzBih => "IZ BiH",
+        }
+    }
+
+    fn parameters(&self) -> MethodParameters {
+        match self {
+            CalculationMethod::MuslimWorldLeague => MethodParameters {
+                fajr_angle: 18.0,
+                isha: IshaRule::Angle(17.0),
+                adjustments: [0; 6],
+            },
+            CalculationMethod::Isna => MethodParameters {
+                fajr_angle: 15.0,
+                isha: IshaRule::Angle(15.0),
+                adjustments: [0; 6],
+            },
+            CalculationMethod::UmmAlQura => MethodParameters {
+                fajr_angle: 18.5,
+                isha: IshaRule::MinutesAfterMaghrib(90),
+                adjustments: [0; 6],
+            },
+            CalculationMethod::Diyanet => MethodParameters {
+                fajr_angle: 18.0,
+                isha: IshaRule::Angle(17.0),
+                adjustments: [0, -7, 5, 4, 7, 0],
+            },
+            CalculationMethod::IzBih => MethodParameters {
+                fajr_angle: 19.0,
+                isha: IshaRule::Angle(17.0),
+                adjustments: [0; 6],
+            },
+        }
+    }
+}
+
+impl FromStr for CalculationMethod {
+    type Err = AppErrorKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().replace('-', "_").as_str() {
+            "mwl" | "muslim_world_league" => Ok(CalculationMethod::MuslimWorldLeague),
+            "isna" => Ok(CalculationMethod::Isna),
+            "umm_al_qura" | "ummalqura" | "makkah" => Ok(CalculationMethod::UmmAlQura),
+            "diyanet" | "turkey" => Ok(CalculationMethod::Diyanet),
+            "iz_bih" | "izbih" | "bih" => Ok(CalculationMethod::IzBih),
+            _ => Err(AppErrorKind::PrayerTimes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsrMethod {
+    /// Shafi'i, Maliki and Hanbali, when the shadow equals the length of the object
+    Standard,
+    /// When the shadow is twice the length of the object
+    Hanafi,
+}
+
+impl AsrMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AsrMethod::Standard => "standard",
+            AsrMethod::Hanafi => "hanafi",
+        }
+    }
+
+    pub fn translation_key(&self) -> TranslationKey {
+        match self {
+            AsrMethod::Standard => TranslationKey::AsrMethodStandard,
+            AsrMethod::Hanafi => TranslationKey::AsrMethodHanafi,
+        }
+    }
+
+    fn shadow_length(&self) -> f64 {
+        match self {
+            AsrMethod::Standard => 1.0,
+            AsrMethod::Hanafi => 2.0,
+        }
+    }
+}
+
+impl FromStr for AsrMethod {
+    type Err = AppErrorKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "standard" | "shafi" | "standardna" | "šafijska" | "safijska" => {
+                Ok(AsrMethod::Standard)
+            }
+            "hanafi" | "hanefi" | "hanefijska" => Ok(AsrMethod::Hanafi),
+            _ => Err(AppErrorKind::PrayerTimes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prayer {
+    Fajr,
+    Sunrise,
+    Dhuhr,
+    Asr,
+    Maghrib,
+    Isha,
+}
+
+impl Prayer {
+    pub const ALL: [Prayer; 6] = [
+        Prayer::Fajr,
+        Prayer::Sunrise,
+        Prayer::Dhuhr,
+        Prayer::Asr,
+        Prayer::Maghrib,
+        Prayer::Isha,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Prayer::Fajr => "fajr",
+            Prayer::Sunrise => "sunrise",
+            Prayer::Dhuhr => "dhuhr",
+            Prayer::Asr => "asr",
+            Prayer::Maghrib => "maghrib",
+            Prayer::Isha => "isha",
+        }
+    }
+
+    pub fn translation_key(&self) -> TranslationKey {
+        match self {
+            Prayer::Fajr => TranslationKey::PrayerFajr,
+            Prayer::Sunrise => TranslationKey::PrayerSunrise,
+            Prayer::Dhuhr => TranslationKey::PrayerDhuhr,
+            Prayer::Asr => TranslationKey::PrayerAsr,
+            Prayer::Maghrib => TranslationKey::PrayerMaghrib,
+            Prayer::Isha => TranslationKey::PrayerIsha,
+        }
+    }
+}
+
+impl FromStr for Prayer {
+    type Err = AppErrorKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "fajr" | "zora" | "sabah" => Ok(Prayer::Fajr),
+            "sunrise" | "izlazak" | "izlazak_sunca" => Ok(Prayer::Sunrise),
+            "dhuhr" | "zuhr" | "podne" => Ok(Prayer::Dhuhr),
+            "asr" | "ikindija" => Ok(Prayer::Asr),
+            "maghrib" | "akšam" | "aksam" => Ok(Prayer::Maghrib),
+            "isha" | "jacija" | "jaciju" => Ok(Prayer::Isha),
+            _ => Err(AppErrorKind::PrayerTimes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrayerTimesConfig {
+    pub method: CalculationMethod,
+    pub asr: AsrMethod,
+}
+
+impl Default for PrayerTimesConfig {
+    fn default() -> Self {
+        Self {
+            method: CalculationMethod::IzBih,
+            asr: AsrMethod::Standard,
+        }
+    }
+}
+
+impl PrayerTimesConfig {
+    /// Reads `PRAYER_TIMES_METHOD` and `PRAYER_TIMES_ASR`, keeping the defaults for the missing ones
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        Self {
+            method: std::env::var("PRAYER_TIMES_METHOD")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.method),
+            asr: std::env::var("PRAYER_TIMES_ASR")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.asr),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrayerTimes {
+    pub fajr: DateTime<Utc>,
+    pub sunrise: DateTime<Utc>,
+    pub dhuhr: DateTime<Utc>,
+    pub asr: DateTime<Utc>,
+    pub maghrib: DateTime<Utc>,
+    pub isha: DateTime<Utc>,
+}
+
+impl PrayerTimes {
+    /// Calculates the prayer times of the local day, rounded to the minute. Returns `None` on
+    /// the days without a sunrise or a sunset, as it happens close to the poles.
+    pub fn calculate(
+        date: NaiveDate,
+        location: Location,
+        config: &PrayerTimesConfig,
+    ) -> Option<Self> {
+        let parameters = config.method.parameters();
+
+        let sunrise = solar::sunrise(date, location)?;
+        let sunset = solar::sunset(date, location)?;
+        let dhuhr = solar::solar_noon(date, location)?;
+        let next_sunrise = solar::sunrise(date.succ_opt()?, location)?;
+        let night = next_sunrise - sunset;
+
+        // At high latitudes the twilight can last all night, so the Fajr and Isha are kept
+        // within a part of the night proportional to their angle
+        let night_portion = |angle: f64| {
+            chrono::Duration::seconds((night.num_seconds() as f64 * angle / 60.0) as i64)
+        };
+
+        let fajr =
+            solar::time_at_altitude(date, location, -parameters.fajr_angle, SunDirection::Rising)
+                .filter(|fajr| *fajr >= sunrise - night_portion(parameters.fajr_angle))
+                .unwrap_or(sunrise - night_portion(parameters.fajr_angle));
+
+        let isha = match parameters.isha {
+            IshaRule::Angle(angle) => {
+                solar::time_at_altitude(date, location, -angle, SunDirection::Setting)
+                    .filter(|isha| *isha <= sunset + night_portion(angle))
+                    .unwrap_or(sunset + night_portion(angle))
+            }
+            IshaRule::MinutesAfterMaghrib(minutes) => sunset + chrono::Duration::minutes(minutes),
+        };
+
+        let declination = solar::sun_position(solar::julian_day(date) + 0.5).declination;
+        let asr_altitude = (1.0
+            / (config.asr.shadow_length()
+                + (location.latitude - declination).abs().to_radians().tan()))
+        .atan()
+        .to_degrees();
+        let asr = solar::time_at_altitude(date, location, asr_altitude, SunDirection::Setting)?;
+
+        let adjusted = |prayer: Prayer, time: DateTime<Utc>| {
+            round_to_minute(
+                time + chrono::Duration::minutes(parameters.adjustments[prayer as usize]),
+            )
+        };
+
+        Some(Self {
+            fajr: adjusted(Prayer::Fajr, fajr),
+            sunrise: adjusted(Prayer::Sunrise, sunrise),
+            dhuhr: adjusted(Prayer::Dhuhr, dhuhr),
+            asr: adjusted(Prayer::Asr, asr),
+            maghrib: adjusted(Prayer::Maghrib, sunset),
+            isha: adjusted(Prayer::Isha, isha),
+        })
+    }
+
+    pub fn time(&self, prayer: Prayer) -> DateTime<Utc> {
+        match prayer {
+            Prayer::Fajr => self.fajr,
+            Prayer::Sunrise => self.sunrise,
+            Prayer::Dhuhr => self.dhuhr,
+            Prayer::Asr => self.asr,
+            Prayer::Maghrib => self.maghrib,
+            Prayer::Isha => self.isha,
+        }
+    }
+}
+
+fn round_to_minute(time: DateTime<Utc>) -> DateTime<Utc> {
+    let time = time + chrono::Duration::seconds(30);
+
+    time.with_second(0)
+        .and_then(|time| time.with_nanosecond(0))
+        .unwrap_or(time)
+}
+
+/// Prayer times settings of the user, falling back to the bot's default for the unset ones
+pub async fn get_user_prayer_times_config(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    default: PrayerTimesConfig,
+) -> PrayerTimesConfig {
+    let settings = sqlx::query!(
+        "SELECT prayer_times_method, asr_method FROM users WHERE chat_id = $1",
+        chat_id
+    )
+    .fetch_optional(pool)
+    .await
+    .unwrap_or_else(|e| {
+        log::error!(
+            "Failed to fetch the prayer times settings of {}: {}",
+            chat_id,
+            e
+        );
+        None
+    });
+
+    match settings {
+        Some(settings) => PrayerTimesConfig {
+            method: settings
+                .prayer_times_method
+                .and_then(|method| method.parse().ok())
+                .unwrap_or(default.method),
+            asr: settings
+                .asr_method
+                .and_then(|asr| asr.parse().ok())
+                .unwrap_or(default.asr),
+        },
+        None => default,
+    }
+}
+
+pub async fn set_user_prayer_times_config(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    username: Option<String>,
+    config: PrayerTimesConfig,
+) -> Result<(), AppErrorKind> {
+    sqlx::query!(
+        "
+            INSERT INTO users (id, chat_id, username, prayer_times_method, asr_method)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (chat_id)
+            DO UPDATE SET prayer_times_method = EXCLUDED.prayer_times_method,
+                asr_method = EXCLUDED.asr_method
+        ",
+        Uuid::new_v4(),
+        chat_id,
+        username,
+        config.method.as_str(),
+        config.asr.as_str()
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        log::error!(
+            "Failed to set the prayer times settings of {}: {}",
+            chat_id,
+            e
+        );
+        AppErrorKind::PrayerTimes
+    })?;
+
+    log::info!("Prayer times settings of {} set to {:?}", chat_id, config);
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use chrono::NaiveDate;
+use sqlx::{Pool, Postgres};
+use tokio::sync::OnceCell;
+
+use crate::{
+    api::CurrentDateResponse,
+    calendar::HijriYmd,
+    error::AppErrorKind,
+    i18n::instance::I18n,
+    provider::{HijriDateProvider, ProviderFuture},
+};
+
+/// Upper bound of the in-memory entries, after which the memory is cleared
+/// and the answers are served from the database again
+const MAX_MEMORY_ENTRIES: usize = 1024;
+
+#[derive(Default)]
+pub struct HijriDateCacheMetrics {
+    memory_hits: AtomicU64,
+    database_hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl std::fmt::Display for HijriDateCacheMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "memory hits: {}, database hits: {}, misses: {}",
+            self.memory_hits.load(Ordering::Relaxed),
+            self.database_hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed)
+        )
+    }
+}
+
+/// Remembers the answers of the wrapped provider per Gregorian day, both in memory and in
+/// the `hijri_date_cache` table. An answer depends only on the day, whichever provider gave it
+/// and whichever timezone the day was picked in. Concurrent lookups of the same day share a
+/// single request.
+pub struct HijriDateCache {
+    provider: Arc<dyn HijriDateProvider>,
+    pool: Pool<Postgres>,
+    i18n: Arc<I18n>,
+    entries: Mutex<HashMap<NaiveDate, Arc<OnceCell<CurrentDateResponse>>>>,
+    metrics: HijriDateCacheMetrics,
+}
+
+impl HijriDateCache {
+    pub fn new(
+        provider: Arc<dyn HijriDateProvider>,
+        pool: Pool<Postgres>,
+        i18n: Arc<I18n>,
+    ) -> Self {
+        Self {
+            provider,
+            pool,
+            i18n,
+            entries: Mutex::new(HashMap::new()),
+            metrics: HijriDateCacheMetrics::default(),
+        }
+    }
+
+    async fn get(&self, date: NaiveDate) -> Result<CurrentDateResponse, AppErrorKind> {
+        let entry = {
+            let mut entries = self
+                .entries
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            if entries.len() >= MAX_MEMORY_ENTRIES && !entries.contains_key(&date) {
+                entries.clear();
+            }
+
+            Arc::clone(entries.entry(date).or_default())
+        };
+
+        let mut loaded = false;
+        let response = entry
+            .get_or_try_init(|| {
+                loaded = true;
+                self.load(date)
+            })
+            .await?;
+
+        if !loaded {
+            self.metrics.memory_hits.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(response.clone())
+    }
+
+    async fn load(&self, date: NaiveDate) -> Result<CurrentDateResponse, AppErrorKind> {
+        let cached = sqlx::query!(
+            "
+                SELECT hijri_year, hijri_month, hijri_day
+                FROM hijri_date_cache
+                WHERE gregorian_date = $1
+            ",
+            date
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("Failed to fetch the cached Hijri date: {}", e);
+            None
+        });
+
+        if let Some(cached) = cached {
+            self.metrics.database_hits.fetch_add(1, Ordering::Relaxed);
+
+            return Ok(CurrentDateResponse::from_hijri_ymd(
+                &HijriYmd {
+                    year: cached.hijri_year,
+                    month: cached.hijri_month as u8,
+                    day: cached.hijri_day as u8,
+                },
+                &self.i18n,
+            ));
+        }
+
+        self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+        let response = self.provider.get_hijri_date(date).await?;
+
+        if let Err(e) = sqlx::query!(
+            "
+                INSERT INTO hijri_date_cache (gregorian_date, hijri_year, hijri_month, hijri_day)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (gregorian_date) DO NOTHING
+            ",
+            date,
+            response.year.parse::<i32>().unwrap_or_default(),
+            response.month_number as i16,
+            response.day_number as i16
+        )
+        .execute(&self.pool)
+        .await
+        {
+            log::error!("Failed to cache the Hijri date for {}: {}", date, e);
+        }
+
+        log::info!("Hijri date cache miss for {}, {}", date, self.metrics);
+
+        Ok(response)
+    }
+}
+
+impl HijriDateProvider for HijriDateCache {
+    fn name(&self) -> &'static str {
+        "cache"
+    }
+
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(self.get(date))
+    }
+
+    fn invalidate_from(&self, date: NaiveDate) -> ProviderFuture<'_, ()> {
+        Box::pin(async move {
+            self.entries
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .retain(|cached_date, _| *cached_date < date);
+
+            sqlx::query!(
+                "DELETE FROM hijri_date_cache WHERE gregorian_date >= $1",
+                date
+            )
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to invalidate the cached Hijri dates: {}", e);
+                AppErrorKind::HijriDateUnavailable
+            })?;
+
+            log::info!("Cached Hijri dates from {} invalidated", date);
+
+            Ok(())
+        })
+    }
+}
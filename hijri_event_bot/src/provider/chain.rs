@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+
+use crate::{
+    api::CurrentDateResponse,
+    error::AppErrorKind,
+    provider::{HijriDateProvider, ProviderFuture},
+};
+
+/// Asks the providers in order and returns the first plausible answer
+#[derive(Default)]
+pub struct HijriDateProviderChain {
+    providers: Vec<Arc<dyn HijriDateProvider>>,
+}
+
+impl HijriDateProviderChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_provider(mut self, provider: Arc<dyn HijriDateProvider>) -> Self {
+        self.providers.push(provider);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+}
+
+impl HijriDateProvider for HijriDateProviderChain {
+    fn name(&self) -> &'static str {
+        "chain"
+    }
+
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(async move {
+            let mut last_error = None;
+
+            for provider in &self.providers {
+                match provider.get_hijri_date(date).await {
+                    Ok(response) if response.is_plausible() => {
+                        log::info!(
+                            "Hijri date {} for {} served by provider: {}",
+                            response,
+                            date,
+                            provider.name()
+                        );
+                        return Ok(response);
+                    }
+                    Ok(response) => {
+                        log::warn!(
+                            "Provider {} returned an implausible Hijri date {} for {}, trying the next one",
+                            provider.name(),
+                            response,
+                            date
+                        );
+                    }
+                    Err(AppErrorKind::HijriDateNotFound) => {
+                        log::debug!(
+                            "Provider {} has no Hijri date for {}, trying the next one",
+                            provider.name(),
+                            date
+                        );
+                    }
+                    Err(err) => {
+                        log::warn!(
+                            "Provider {} failed to get the Hijri date for {}: {:?}, trying the next one",
+                            provider.name(),
+                            date,
+                            err
+                        );
+                        last_error = Some(err);
+                    }
+                }
+            }
+
+            log::error!("No provider could get the Hijri date for {}", date);
+            Err(last_error.unwrap_or(AppErrorKind::HijriDateUnavailable))
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use sqlx::{Pool, Postgres, postgres::PgListener};
+
+use crate::{
+    api::CurrentDateResponse,
+    calendar::HijriYmd,
+    error::AppErrorKind,
+    i18n::instance::I18n,
+    provider::{HijriDateProvider, ProviderFuture},
+};
+
+/// Channel on which the `hijri_date_overrides` table announces the first date changed by an edit
+const OVERRIDES_CHANNEL: &str = "hijri_date_overrides";
+
+/// Serves the dates which were manually entered into the `hijri_date_overrides` table
+pub struct DatabaseOverrideProvider {
+    pool: Pool<Postgres>,
+    i18n: Arc<I18n>,
+}
+
+impl DatabaseOverrideProvider {
+    pub fn new(pool: Pool<Postgres>, i18n: Arc<I18n>) -> Self {
+        Self { pool, i18n }
+    }
+}
+
+impl HijriDateProvider for DatabaseOverrideProvider {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(async move {
+            let hijri_date_override = sqlx::query!(
+                "
+                    SELECT hijri_year, hijri_month, hijri_day
+                    FROM hijri_date_overrides
+                    WHERE gregorian_date = $1
+                ",
+                date
+            )
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to fetch Hijri date override: {}", e);
+                AppErrorKind::HijriDateOverride
+            })?
+            .ok_or(AppErrorKind::HijriDateNotFound)?;
+
+            Ok(CurrentDateResponse::from_hijri_ymd(
+                &HijriYmd {
+                    year: hijri_date_override.hijri_year,
+                    month: hijri_date_override.hijri_month as u8,
+                    day: hijri_date_override.hijri_day as u8,
+                },
+                &self.i18n,
+            ))
+        })
+    }
+}
+
+/// Drops the answers which the `provider` remembered from the date of every override edited in
+/// the database, as the overrides are entered by hand
+pub async fn watch_overrides(pool: Pool<Postgres>, provider: Arc<dyn HijriDateProvider>) {
+    let mut listener = match PgListener::connect_with(&pool).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!("Failed to listen for the Hijri date overrides: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = listener.listen(OVERRIDES_CHANNEL).await {
+        log::error!("Failed to listen for the Hijri date overrides: {}", e);
+        return;
+    }
+
+    loop {
+        let notification = match listener.recv().await {
+            Ok(notification) => notification,
+            Err(e) => {
+                log::error!("Stopped listening for the Hijri date overrides: {}", e);
+                return;
+            }
+        };
+
+        match notification.payload().parse::<NaiveDate>() {
+            Ok(date) => {
+                log::info!("Hijri date override of {} changed", date);
+                let _ = provider.invalidate_from(date).await;
+            }
+            Err(e) => log::error!("Invalid Hijri date override notification: {}", e),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::pin::Pin;
+
+use chrono::NaiveDate;
+
+use crate::{api::CurrentDateResponse, calendar::DEFAULT_TIMEZONE, error::AppErrorKind};
+
+pub mod cache;
+pub mod chain;
+pub mod database;
+pub mod month_start;
+
+pub type ProviderFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, AppErrorKind>> + Send + 'a>>;
+
+pub trait HijriDateProvider: Send + Sync + 'static {
+    /// Name of the provider, used in the logs and in the `HIJRI_DATE_PROVIDERS` configuration
+    fn name(&self) -> &'static str;
+
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse>;
+
+    fn get_current_hijri_date_in(
+        &self,
+        timezone: chrono_tz::Tz,
+    ) -> ProviderFuture<'_, CurrentDateResponse> {
+        let date_now = chrono::Utc::now().with_timezone(&timezone);
+
+        self.get_hijri_date(date_now.date_naive())
+    }
+
+    fn get_current_hijri_date(&self) -> ProviderFuture<'_, CurrentDateResponse> {
+        self.get_current_hijri_date_in(DEFAULT_TIMEZONE)
+    }
+
+    /// Drops the answers which the provider remembered for the given date and the dates after it
+    fn invalidate_from(&self, _date: NaiveDate) -> ProviderFuture<'_, ()> {
+        Box::pin(async { Ok(()) })
+    }
+}
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    api::CurrentDateResponse,
+    calendar::{HijriCalendar, HijriYmd},
+    error::AppErrorKind,
+    i18n::instance::I18n,
+    provider::{HijriDateProvider, ProviderFuture},
+};
+
+/// Serves the dates which follow an officially announced start of a Hijri month,
+/// e.g. after the moon sighting announcement of the Islamic Community
+pub struct MonthStartProvider {
+    pool: Pool<Postgres>,
+    calendar: Arc<HijriCalendar>,
+    i18n: Arc<I18n>,
+}
+
+impl MonthStartProvider {
+    pub fn new(pool: Pool<Postgres>, calendar: Arc<HijriCalendar>, i18n: Arc<I18n>) -> Self {
+        Self {
+            pool,
+            calendar,
+            i18n,
+        }
+    }
+
+    pub async fn announce(
+        &self,
+        year: i32,
+        month: u8,
+        start: NaiveDate,
+    ) -> Result<(), AppErrorKind> {
+        sqlx::query!(
+            "
+                INSERT INTO hijri_month_starts (hijri_year, hijri_month, gregorian_date)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (hijri_year, hijri_month)
+                DO UPDATE SET gregorian_date = EXCLUDED.gregorian_date, created_at = CURRENT_TIMESTAMP
+            ",
+            year,
+            month as i16,
+            start
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!(
+                "Failed to save the start of the Hijri month {}/{}: {}",
+                month,
+                year,
+                e
+            );
+            AppErrorKind::MonthStartAnnouncement
+        })?;
+
+        log::info!(
+            "Start of the Hijri month {}/{} announced for {}",
+            month,
+            year,
+            start
+        );
+
+        Ok(())
+    }
+}
+
+impl HijriDateProvider for MonthStartProvider {
+    fn name(&self) -> &'static str {
+        "announcement"
+    }
+
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(async move {
+            let previous = sqlx::query_as!(
+                MonthStart,
+                "
+                    SELECT hijri_year AS year, hijri_month AS month, gregorian_date AS start
+                    FROM hijri_month_starts
+                    WHERE gregorian_date <= $1
+                    ORDER BY gregorian_date DESC
+                    LIMIT 1
+                ",
+                date
+            )
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to fetch the Hijri month start: {}", e);
+                AppErrorKind::MonthStartAnnouncement
+            })?;
+
+            let next = sqlx::query_as!(
+                MonthStart,
+                "
+                    SELECT hijri_year AS year, hijri_month AS month, gregorian_date AS start
+                    FROM hijri_month_starts
+                    WHERE gregorian_date > $1
+                    ORDER BY gregorian_date
+                    LIMIT 1
+                ",
+                date
+            )
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to fetch the next Hijri month start: {}", e);
+                AppErrorKind::MonthStartAnnouncement
+            })?;
+
+            let hijri_date =
+                shifted_hijri_date(&self.calendar, date, previous.as_ref(), next.as_ref())
+                    .ok_or(AppErrorKind::HijriDateNotFound)?;
+
+            Ok(CurrentDateResponse::from_hijri_ymd(&hijri_date, &self.i18n))
+        })
+    }
+}
+
+/// Officially announced start of a Hijri month
+#[derive(Debug, Clone, Copy)]
+pub struct MonthStart {
+    pub year: i32,
+    pub month: i16,
+    pub start: NaiveDate,
+}
+
+impl MonthStart {
+    fn first_day(&self) -> HijriYmd {
+        HijriYmd {
+            year: self.year,
+            month: self.month as u8,
+            day: 1,
+        }
+    }
+}
+
+/// Hijri date of the `date` following the announced month starts around it. The shift of the
+/// `previous` announcement from the calendar stays in effect until the `next` one. When the next
+/// month starts later than the shifted calendar, its eve is still in the month before it.
+/// `None` means the announcements don't decide the date.
+pub fn shifted_hijri_date(
+    calendar: &HijriCalendar,
+    date: NaiveDate,
+    previous: Option<&MonthStart>,
+    next: Option<&MonthStart>,
+) -> Option<HijriYmd> {
+    let shift = match previous {
+        Some(previous) => previous.start - calendar.to_gregorian(&previous.first_day()).ok()?,
+        None => chrono::Duration::zero(),
+    };
+    let shifted = calendar.to_hijri(date.checked_sub_signed(shift)?).ok()?;
+
+    if let Some(next) = next
+        && (shifted.year, shifted.month) >= (next.year, next.month as u8)
+    {
+        let (year, month) = if next.month == 1 {
+            (next.year - 1, 12)
+        } else {
+            (next.year, next.month as u8 - 1)
+        };
+        let month_start = calendar
+            .to_gregorian(&HijriYmd {
+                year,
+                month,
+                day: 1,
+            })
+            .ok()?
+            .checked_add_signed(shift)?;
+
+        return Some(HijriYmd {
+            year,
+            month,
+            day: u8::try_from((date - month_start).num_days() + 1).ok()?,
+        });
+    }
+
+    previous.map(|_previous| shifted)
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    i18n::{instance::I18n, translation_key::TranslationKey},
+    prayer_times::PrayerTimes,
+};
+
+pub const RAMADAN: u8 = 9;
+
+/// Minutes before Fajr at which the suhoor reminder is sent
+pub const SUHOOR_MINUTES_BEFORE: i64 = 30;
+
+/// Day of Ramadan before which the Sadaqat al-Fitr and Eid reminder is sent
+const SADAQAT_AL_FITR_DAY: u8 = 28;
+
+/// Odd nights of the last ten nights of Ramadan, in which Laylat al-Qadr is sought
+const ODD_NIGHTS: [u8; 5] = [21, 23, 25, 27, 29];
+
+/// Reminders sent at the prayer times of the days of Ramadan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FastReminder {
+    Suhoor,
+    Iftar,
+}
+
+impl FastReminder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FastReminder::Suhoor => "suhoor",
+            FastReminder::Iftar => "iftar",
+        }
+    }
+
+    pub fn time(&self, prayer_times: &PrayerTimes) -> DateTime<Utc> {
+        match self {
+            FastReminder::Suhoor => {
+                prayer_times.fajr - chrono::Duration::minutes(SUHOOR_MINUTES_BEFORE)
+            }
+            FastReminder::Iftar => prayer_times.maghrib,
+        }
+    }
+
+    /// Reminders whose time is within the `window` ending at the moment
+    pub fn due(
+        prayer_times: &PrayerTimes,
+        at: DateTime<Utc>,
+        window: chrono::Duration,
+    ) -> Vec<FastReminder> {
+        [FastReminder::Suhoor, FastReminder::Iftar]
+            .into_iter()
+            .filter(|reminder| {
+                let time = reminder.time(prayer_times);
+                time <= at && at < time + window
+            })
+            .collect()
+    }
+
+    pub fn message(
+        &self,
+        prayer_times: &PrayerTimes,
+        timezone: chrono_tz::Tz,
+        i18n: &I18n,
+    ) -> String {
+        let mut args = HashMap::new();
+        args.insert("minutes", SUHOOR_MINUTES_BEFORE.to_string());
+        args.insert("fajr", local_time(prayer_times.fajr, timezone));
+        args.insert("maghrib", local_time(prayer_times.maghrib, timezone));
+
+        match self {
+            FastReminder::Suhoor => i18n.t_with_args(&TranslationKey::RamadanSuhoor, args),
+            FastReminder::Iftar => i18n.t_with_args(&TranslationKey::RamadanIftar, args),
+        }
+    }
+}
+
+fn local_time(time: DateTime<Utc>, timezone: chrono_tz::Tz) -> String {
+    time.with_timezone(&timezone).format("%H:%M").to_string()
+}
+
+/// Messages sent in the evening at which the `day` of Ramadan starts, with the times of its
+/// fast when the prayer times of the user are known
+pub fn evening_messages(
+    day: u8,
+    prayer_times: Option<&PrayerTimes>,
+    timezone: chrono_tz::Tz,
+    i18n: &I18n,
+) -> Vec<String> {
+    let mut args = HashMap::new();
+    args.insert("day", day.to_string());
+
+    let mut day_message = i18n.t_with_args(&TranslationKey::RamadanDay, args.clone());
+    if let Some(prayer_times) = prayer_times {
+        let mut times_args = HashMap::new();
+        times_args.insert(
+            "suhoor",
+            local_time(FastReminder::Suhoor.time(prayer_times), timezone),
+        );
+        times_args.insert("fajr", local_time(prayer_times.fajr, timezone));
+        times_args.insert("maghrib", local_time(prayer_times.maghrib, timezone));
+
+        day_message.push_str("\n\n");
+        day_message.push_str(&i18n.t_with_args(&TranslationKey::RamadanDayTimes, times_args));
+    }
+
+    let mut messages = vec![day_message];
+
+    if ODD_NIGHTS.contains(&day) {
+        messages.push(i18n.t_with_args(&TranslationKey::RamadanOddNight, args));
+    }
+
+    if day == SADAQAT_AL_FITR_DAY {
+        messages.push(i18n.t(&TranslationKey::RamadanSadaqatAlFitr));
+    }
+
+    messages
+}
@@ -1,23 +1,41 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    pin::Pin,
+    sync::Arc,
+};
 
 use bot_core::{
-    bot_core::BotCore,
     db::{
         postgres_metadata_store::{JobCallbacksExtension, PostgresMetadataStore},
         postgres_notification_store::PostgresNotificationStore,
     },
+    dispatcher::{Notification, NotificationDispatcher},
+    job::{JobRehydrator, one_shot_job_at},
 };
-use sqlx::{Pool, Postgres};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Pool, Postgres, types::Uuid};
 use teloxide::{Bot, types::ChatId};
 use tokio_cron_scheduler::{
     Job, JobScheduler, SimpleJobCode, SimpleNotificationCode, job::job_data_prost::JobStoredData,
 };
 
 use crate::{
-    api::HijriApi,
+    calendar::DEFAULT_TIMEZONE,
+    delivery_time::get_user_delivery_time,
     error::AppErrorKind,
+    event::EventCatalog,
+    fasting::{WHITE_DAYS, white_days},
     i18n::{instance::I18n, translation_key::TranslationKey},
+    islamic_day::{is_delivery_due, is_evening_due},
     job::{JobExtensionType, JobExtraData},
+    location::{Location, get_user_location},
+    notification::{NotificationCategory, is_notification_enabled},
+    prayer_reminder::{due_reminders, get_prayer_reminders, next_reminder_time},
+    prayer_times::{PrayerTimes, PrayerTimesConfig, get_user_prayer_times_config},
+    provider::HijriDateProvider,
+    ramadan::{FastReminder, RAMADAN, evening_messages},
+    sunnah_fast::{SHAWWAL_FASTS, SunnahFast, get_shawwal_fasts},
+    timezone::get_user_timezone,
 };
 
 struct SchedulerCallbacks;
@@ -42,7 +60,11 @@ impl JobCallbacksExtension for SchedulerCallbacks {
                 })?;
 
             sqlx::query!(
-                "INSERT INTO job_extensions (job_id, type) VALUES ($1, $2)",
+                "
+                    INSERT INTO job_extensions (job_id, type)
+                    VALUES ($1, $2)
+                    ON CONFLICT (job_id) DO NOTHING
+                ",
                 job.id,
                 extra_job_data.extension_type as i32
             )
@@ -57,23 +79,241 @@ impl JobCallbacksExtension for SchedulerCallbacks {
                 tokio_cron_scheduler::JobSchedulerError::CantAdd
             })?;
 
+            // A user has a single job of each type, so adding a second one fails on the unique
+            // (user_id, extension_type) constraint, while a rehydrated job keeps its row
+            if let Some(chat_id) = extra_job_data.chat_id {
+                sqlx::query!(
+                    "
+                        INSERT INTO users_jobs (id, job_id, user_id, extension_type)
+                        SELECT $1, $2, id, $4 FROM users WHERE chat_id = $3
+                        AND NOT EXISTS (SELECT 1 FROM users_jobs WHERE job_id = $2)
+                    ",
+                    Uuid::new_v4(),
+                    job.id,
+                    chat_id,
+                    extra_job_data.extension_type as i32
+                )
+                .execute(&mut **tx)
+                .await
+                .map_err(|e| {
+                    log::error!(
+                        "Failed to insert the user of job id: {}, error: {}",
+                        job.id,
+                        e
+                    );
+                    tokio_cron_scheduler::JobSchedulerError::CantAdd
+                })?;
+            }
+
             Ok(())
         })
     }
 }
 
+#[derive(Clone)]
 pub struct Scheduler {
-    api: Arc<HijriApi>,
+    provider: Arc<dyn HijriDateProvider>,
+    pool: Pool<Postgres>,
     sched: JobScheduler,
     i18n: Arc<I18n>,
+    prayer_times_config: PrayerTimesConfig,
+    dispatcher: NotificationDispatcher,
+}
+
+/// What the chain of the prayer reminder jobs of a user needs to add its next job
+#[derive(Clone)]
+struct PrayerReminderContext {
+    pool: Pool<Postgres>,
+    i18n: Arc<I18n>,
+    bot: Arc<Bot>,
+    prayer_times_config: PrayerTimesConfig,
+    dispatcher: NotificationDispatcher,
+    chat_id: i64,
+}
+
+impl PrayerReminderContext {
+    /// Sends the reminders due at the moment
+    async fn send_due_reminders(&self, at: DateTime<Utc>) {
+        if !is_notification_enabled(
+            &self.pool,
+            self.chat_id,
+            NotificationCategory::PrayerReminders,
+        )
+        .await
+        {
+            return;
+        }
+
+        let (reminders, location) = match (
+            get_prayer_reminders(&self.pool, self.chat_id).await,
+            get_user_location(&self.pool, self.chat_id).await,
+        ) {
+            (Ok(reminders), Some(location)) => (reminders, location),
+            _ => return,
+        };
+        let timezone = get_user_timezone(&self.pool, self.chat_id).await;
+        let config =
+            get_user_prayer_times_config(&self.pool, self.chat_id, self.prayer_times_config).await;
+
+        for (reminder, prayer_time) in due_reminders(&reminders, location, timezone, &config, at) {
+            let mut args = HashMap::new();
+            args.insert("prayer", self.i18n.t(&reminder.prayer.translation_key()));
+            args.insert(
+                "time",
+                prayer_time
+                    .with_timezone(&timezone)
+                    .format("%H:%M")
+                    .to_string(),
+            );
+            args.insert("minutes", reminder.minutes_before.to_string());
+
+            let key = if reminder.minutes_before == 0 {
+                TranslationKey::PrayerReminderAt
+            } else {
+                TranslationKey::PrayerReminderBefore
+            };
+
+            self.dispatcher
+                .send(
+                    &self.bot,
+                    &self.pool,
+                    ChatId(self.chat_id),
+                    &self.i18n.t_with_args(&key, args),
+                )
+                .await;
+        }
+    }
+
+    /// Adds the job of the first reminder after the given moment. The job sends the reminders
+    /// and adds the job of the next one, as the prayer times move every day.
+    fn schedule_next(
+        self,
+        sched: JobScheduler,
+        after: DateTime<Utc>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), AppErrorKind>> + Send>> {
+        Box::pin(async move {
+            let reminders = get_prayer_reminders(&self.pool, self.chat_id).await?;
+            let location = match get_user_location(&self.pool, self.chat_id).await {
+                Some(location) if !reminders.is_empty() => location,
+                _ => return Ok(()),
+            };
+            let timezone = get_user_timezone(&self.pool, self.chat_id).await;
+            let config =
+                get_user_prayer_times_config(&self.pool, self.chat_id, self.prayer_times_config)
+                    .await;
+
+            let next = match next_reminder_time(&reminders, location, timezone, &config, after) {
+                Some(next) => next,
+                None => {
+                    log::warn!("No upcoming prayer reminder for {}", self.chat_id);
+                    return Ok(());
+                }
+            };
+
+            let prayer_reminder_job = self.job_at(next)?;
+
+            sched.add(prayer_reminder_job).await.map_err(|err| {
+                log::error!("Failed to schedule prayer reminder job: {}", err);
+                AppErrorKind::PrayerReminder
+            })?;
+
+            log::info!("Prayer reminder of {} scheduled for {}", self.chat_id, next);
+
+            Ok(())
+        })
+    }
+
+    /// Job sending the reminders due at the moment and adding the job of the next one
+    fn job_at(&self, next: DateTime<Utc>) -> Result<Job, AppErrorKind> {
+        let extra_data = serde_json::to_vec(&JobExtraData {
+            extension_type: JobExtensionType::PrayerReminder,
+            chat_id: Some(self.chat_id),
+        })
+        .map_err(|err| {
+            log::error!("Failed to serialize job extra data: {}", err);
+            AppErrorKind::PrayerReminder
+        })?;
+
+        let context = self.clone();
+        let prayer_reminder_job = one_shot_job_at(next, extra_data, move |uuid, sched| {
+            let context = context.clone();
+
+            Box::pin(async move {
+                // The reminders of a job running late, e.g. after a restart, are skipped
+                if Utc::now() - next < chrono::Duration::minutes(LATE_PRAYER_REMINDER_MINUTES) {
+                    context.send_due_reminders(next).await;
+                }
+
+                // The one shot jobs stay in the store after they run. The job is removed before
+                // the next one is added, as a user has a single prayer reminder job.
+                if let Err(err) = sched.remove(&uuid).await {
+                    log::error!("Failed to remove prayer reminder job: {}", err);
+                }
+
+                if context
+                    .clone()
+                    .schedule_next(sched.clone(), next)
+                    .await
+                    .is_ok()
+                {
+                    return;
+                }
+
+                // Without a job the reminders of the user would stop for good, so a job a few
+                // minutes later tries to schedule the next one again
+                let retry_at =
+                    Utc::now() + chrono::Duration::minutes(PRAYER_REMINDER_RETRY_MINUTES);
+                let retry_job = match context.job_at(retry_at) {
+                    Ok(job) => job,
+                    Err(_err) => return,
+                };
+
+                match sched.add(retry_job).await {
+                    Ok(_) => log::warn!(
+                        "Failed to schedule the next prayer reminder of {}, retrying at {}",
+                        context.chat_id,
+                        retry_at
+                    ),
+                    Err(err) => log::error!(
+                        "Failed to schedule the next prayer reminder of {}: {}",
+                        context.chat_id,
+                        err
+                    ),
+                }
+            })
+        })
+        .map_err(|err| {
+            log::error!("Failed to create prayer reminder job: {}", err);
+            AppErrorKind::PrayerReminder
+        })?;
+
+        Ok(prayer_reminder_job)
+    }
 }
 
-const DAY_BEFORE_FIRST_WHITE_DAY: u8 = 12;
+/// The evening jobs tick every 15 minutes and check whether the evening of the user (the
+/// sunset, or 18:00 in the user's timezone) or the delivery time the user has chosen has just
+/// started
+const EVENING_CHECK_SCHEDULE: &str = "0 */15 * * * *";
+const EVENING_CHECK_MINUTES: i64 = 15;
+
+/// The Ramadan job ticks every minute, for the suhoor and iftar reminders
+const RAMADAN_CHECK_SCHEDULE: &str = "0 * * * * *";
+
+/// Minutes after which a prayer reminder is too late to be sent
+const LATE_PRAYER_REMINDER_MINUTES: i64 = 5;
+
+/// Minutes after which the next prayer reminder is scheduled again, when scheduling it failed
+const PRAYER_REMINDER_RETRY_MINUTES: i64 = 5;
+
+/// Hours back in which the evening notifications missed while the bot was down are still sent
+/// on startup
+const CATCH_UP_HOURS: i64 = 3;
 
 impl Scheduler {
     pub async fn new(
         pool: Pool<Postgres>,
-        api: Arc<HijriApi>,
+        provider: Arc<dyn HijriDateProvider>,
         i18n: Arc<I18n>,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let postgres_metadata_store =
@@ -81,7 +321,7 @@ impl Scheduler {
 
         let mut sched = JobScheduler::new_with_storage_and_code(
             Box::new(postgres_metadata_store),
-            Box::new(PostgresNotificationStore::new(pool)),
+            Box::new(PostgresNotificationStore::new(pool.clone())),
             Box::new(SimpleJobCode::default()),
             Box::new(SimpleNotificationCode::default()),
             200,
@@ -98,62 +338,521 @@ impl Scheduler {
 
         sched.start().await?;
 
-        Ok(Self { sched, api, i18n })
+        Ok(Self {
+            sched,
+            provider,
+            pool,
+            i18n,
+            prayer_times_config: PrayerTimesConfig::default(),
+            dispatcher: NotificationDispatcher::new(),
+        })
+    }
+
+    /// Default prayer times settings of the users who haven't changed them
+    pub fn with_prayer_times_config(mut self, prayer_times_config: PrayerTimesConfig) -> Self {
+        self.prayer_times_config = prayer_times_config;
+        self
+    }
+
+    /// Dispatcher the notifications of the bot are sent through, within the limits of Telegram
+    pub fn with_dispatcher(mut self, dispatcher: NotificationDispatcher) -> Self {
+        self.dispatcher = dispatcher;
+        self
     }
 
-    pub async fn schedule_white_days_message(
+    /// Adds the jobs persisted before a restart back to the scheduler under their ids, as only
+    /// their data outlives the process
+    pub async fn rehydrate_jobs(
         &self,
         bot: Bot,
-        chat_id: i64,
+        events: Arc<EventCatalog>,
     ) -> Result<(), AppErrorKind> {
-        let api = Arc::clone(&self.api);
-        let i18n = Arc::clone(&self.i18n);
         let bot = Arc::new(bot);
+        let mut rehydrator = JobRehydrator::new(self.pool.clone());
 
-        // This job will run at 18:00 every day to check if it's the 12th of the month
-        // and send a notification if it is
-        let mut white_days_message_job = Job::new_async("0 0 18 * * *", move |_uuid, _l| {
-            let api = Arc::clone(&api);
-            let i18n = Arc::clone(&i18n);
+        for extension_type in JobExtensionType::ALL {
+            let scheduler = self.clone();
             let bot = Arc::clone(&bot);
+            let events = Arc::clone(&events);
 
-            Box::pin(async move {
-                let current_date = api
-                    .get_current_hijri_date()
-                    .await
-                    .map_err(|_err| AppErrorKind::WhiteDaysMessage);
+            rehydrator = rehydrator.with_factory(extension_type as i32, move |data| {
+                scheduler.rebuild_job(extension_type, data, Arc::clone(&bot), Arc::clone(&events))
+            });
+        }
 
-                match current_date {
-                    Ok(date_response) => {
-                        if date_response.day_number == DAY_BEFORE_FIRST_WHITE_DAY
-                            // We will skip the notification for the month of Ramadan
-                            && date_response.month_number != 9
-                        {
-                            let mut args = HashMap::new();
-                            args.insert("month", date_response.month_name);
+        rehydrator.run(&self.sched).await.map_err(|err| {
+            log::error!("Failed to rehydrate the persisted jobs: {}", err);
+            AppErrorKind::SchedulerInitialization
+        })?;
 
-                            let message =
-                                i18n.t_with_args(&TranslationKey::WhiteDaysNotification, args);
-                            BotCore::send_message(&bot, ChatId(chat_id), message).await;
-                            return;
-                        }
+        Ok(())
+    }
 
-                        log::info!(
-                            "Current Hijri date is {}, not scheduling white days message.",
-                            date_response
-                        );
-                    }
-                    Err(_err) => {
-                        log::error!("Current Hijri date fetch error");
-                    }
-                }
+    /// Sends the message to every active user who hasn't turned the category off
+    pub async fn announce(&self, bot: &Bot, category: NotificationCategory, message: String) {
+        let chat_ids = match sqlx::query_scalar!(
+            "
+                SELECT chat_id
+                FROM users
+                WHERE active AND NOT EXISTS (
+                    SELECT 1
+                    FROM notification_preferences
+                    WHERE user_id = users.id AND category = $1 AND NOT enabled
+                )
+            ",
+            category.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(chat_ids) => chat_ids,
+            Err(e) => {
+                log::error!("Failed to fetch users for the announcement: {}", e);
+                return;
+            }
+        };
+
+        let notifications = chat_ids
+            .into_iter()
+            .map(|chat_id| Notification::new(ChatId(chat_id), vec![message.clone()]))
+            .collect();
+        self.dispatcher
+            .broadcast(bot, &self.pool, notifications)
+            .await;
+    }
+
+    /// Sends the white days, event and Sunnah fast notifications which were due in the last
+    /// [`CATCH_UP_HOURS`] hours and are missing from the `deliveries` table, e.g. because the
+    /// bot was down at the evening of the users
+    pub async fn catch_up(&self, bot: Bot, events: Arc<EventCatalog>) {
+        let window = chrono::Duration::hours(CATCH_UP_HOURS);
+
+        self.send_white_days_messages(&bot, window).await;
+        self.send_event_reminders(&bot, &events, window).await;
+
+        let sunnah_fast_types: Vec<i32> = SunnahFast::ALL
+            .iter()
+            .map(|fast| fast.extension_type() as i32)
+            .collect();
+        let sunnah_fast_jobs = match sqlx::query!(
+            "
+                SELECT users.chat_id, users_jobs.extension_type
+                FROM users_jobs
+                JOIN users ON users.id = users_jobs.user_id
+                WHERE users.active AND users_jobs.extension_type = ANY($1)
+            ",
+            &sunnah_fast_types
+        )
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                log::error!(
+                    "Failed to fetch the Sunnah fast reminders to catch up: {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        for job in sunnah_fast_jobs {
+            if let Some(fast) = SunnahFast::from_extension_type(job.extension_type) {
+                self.send_sunnah_fast_reminder(&bot, job.chat_id, fast, window)
+                    .await;
+            }
+        }
+
+        log::info!("Missed notifications caught up");
+    }
+
+    /// Builds the persisted job of the extension type again from its stored data
+    fn rebuild_job(
+        &self,
+        extension_type: JobExtensionType,
+        data: &JobStoredData,
+        bot: Arc<Bot>,
+        events: Arc<EventCatalog>,
+    ) -> Option<Job> {
+        let extra_data: JobExtraData = serde_json::from_slice(&data.extra)
+            .map_err(|err| {
+                log::error!("Failed to deserialize job extra data: {}", err);
             })
+            .ok()?;
+
+        let job = match (extension_type, extra_data.chat_id) {
+            (JobExtensionType::WhiteDaysMessage, _) => self.white_days_message_job(bot),
+            (JobExtensionType::EventReminder, _) => self.event_reminder_job(bot, events),
+            (JobExtensionType::PrayerReminder, Some(chat_id)) => {
+                let next = DateTime::from_timestamp(data.next_tick as i64, 0)?;
+                self.prayer_reminder_context(bot, chat_id).job_at(next)
+            }
+            (JobExtensionType::RamadanReminder, _) => self.ramadan_job(bot),
+            (extension_type, Some(chat_id)) => {
+                let fast = SunnahFast::from_extension_type(extension_type as i32)?;
+                self.sunnah_fast_job(bot, chat_id, fast)
+            }
+            (extension_type, None) => {
+                log::error!("Job of type {:?} is missing its user", extension_type);
+                return None;
+            }
+        };
+
+        job.ok()
+    }
+
+    /// Jobs of the user with the given extension type
+    async fn user_job_ids(
+        &self,
+        chat_id: i64,
+        extension_type: JobExtensionType,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            "
+                SELECT job_extensions.job_id
+                FROM job_extensions
+                JOIN users_jobs ON users_jobs.job_id = job_extensions.job_id
+                JOIN users ON users.id = users_jobs.user_id
+                WHERE users.chat_id = $1 AND job_extensions.type = $2
+            ",
+            chat_id,
+            extension_type as i32,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Deletes the data of the user and removes their jobs from the running scheduler
+    pub async fn unsubscribe_user(&self, chat_id: i64) -> Result<(), AppErrorKind> {
+        let job_ids = delete_user_data(&self.pool, chat_id).await.map_err(|e| {
+            log::error!("Failed to delete the data of {}: {}", chat_id, e);
+            AppErrorKind::Unsubscribe
+        })?;
+
+        for job_id in job_ids {
+            self.sched.remove(&job_id).await.map_err(|err| {
+                log::error!("Failed to remove job {} of {}: {}", job_id, chat_id, err);
+                AppErrorKind::Unsubscribe
+            })?;
+        }
+
+        log::info!("User {} unsubscribed", chat_id);
+
+        Ok(())
+    }
+
+    /// Replaces the pending prayer reminder job of the user with one following the current
+    /// reminders, location and prayer times settings
+    pub async fn schedule_prayer_reminders(
+        &self,
+        bot: Bot,
+        chat_id: i64,
+    ) -> Result<(), AppErrorKind> {
+        let pending_job_ids = self
+            .user_job_ids(chat_id, JobExtensionType::PrayerReminder)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to fetch the prayer reminder jobs: {}", e);
+                AppErrorKind::PrayerReminder
+            })?;
+
+        for job_id in pending_job_ids {
+            self.sched.remove(&job_id).await.map_err(|err| {
+                log::error!("Failed to remove prayer reminder job: {}", err);
+                AppErrorKind::PrayerReminder
+            })?;
+        }
+
+        self.prayer_reminder_context(Arc::new(bot), chat_id)
+            .schedule_next(self.sched.clone(), Utc::now())
+            .await
+    }
+
+    fn prayer_reminder_context(&self, bot: Arc<Bot>, chat_id: i64) -> PrayerReminderContext {
+        PrayerReminderContext {
+            pool: self.pool.clone(),
+            i18n: Arc::clone(&self.i18n),
+            bot,
+            prayer_times_config: self.prayer_times_config,
+            dispatcher: self.dispatcher.clone(),
+            chat_id,
+        }
+    }
+
+    /// Sunnah fasts the user is reminded of
+    pub async fn get_sunnah_fast_reminders(
+        &self,
+        chat_id: i64,
+    ) -> Result<Vec<SunnahFast>, AppErrorKind> {
+        let extension_types = sqlx::query_scalar!(
+            "
+                SELECT job_extensions.type
+                FROM job_extensions
+                JOIN users_jobs ON users_jobs.job_id = job_extensions.job_id
+                JOIN users ON users.id = users_jobs.user_id
+                WHERE users.chat_id = $1
+            ",
+            chat_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!(
+                "Failed to fetch the Sunnah fast reminders of {}: {}",
+                chat_id,
+                e
+            );
+            AppErrorKind::SunnahFastReminder
+        })?;
+
+        Ok(SunnahFast::ALL
+            .into_iter()
+            .filter(|fast| extension_types.contains(&(fast.extension_type() as i32)))
+            .collect())
+    }
+
+    /// Reminds the user of the Sunnah fast in the evening before its days, or at the user's
+    /// delivery time, unless the user is already reminded of it
+    pub async fn schedule_sunnah_fast_reminder(
+        &self,
+        bot: Bot,
+        chat_id: i64,
+        fast: SunnahFast,
+    ) -> Result<(), AppErrorKind> {
+        let existing_job_ids = self
+            .user_job_ids(chat_id, fast.extension_type())
+            .await
+            .map_err(|e| {
+                log::error!("Failed to check for existing job: {}", e);
+                AppErrorKind::SunnahFastReminder
+            })?;
+
+        if !existing_job_ids.is_empty() {
+            log::info!("{:?} reminder of {} already exists.", fast, chat_id);
+            return Ok(());
+        }
+
+        let sunnah_fast_job = self.sunnah_fast_job(Arc::new(bot), chat_id, fast)?;
+
+        self.sched.add(sunnah_fast_job).await.map_err(|err| {
+            log::error!("Failed to schedule Sunnah fast reminder job: {}", err);
+            AppErrorKind::SunnahFastReminder
+        })?;
+
+        log::info!("{:?} reminder of {} scheduled successfully.", fast, chat_id);
+
+        Ok(())
+    }
+
+    fn sunnah_fast_job(
+        &self,
+        bot: Arc<Bot>,
+        chat_id: i64,
+        fast: SunnahFast,
+    ) -> Result<Job, AppErrorKind> {
+        let scheduler = self.clone();
+
+        let mut sunnah_fast_job = Job::new_async(EVENING_CHECK_SCHEDULE, move |_uuid, _l| {
+            let scheduler = scheduler.clone();
+            let bot = Arc::clone(&bot);
+
+            Box::pin(async move {
+                scheduler
+                    .send_sunnah_fast_reminder(
+                        &bot,
+                        chat_id,
+                        fast,
+                        chrono::Duration::minutes(EVENING_CHECK_MINUTES),
+                    )
+                    .await;
+            })
+        })
+        .map_err(|err| {
+            log::error!("Failed to create Sunnah fast reminder job: {}", err);
+            AppErrorKind::SunnahFastReminder
+        })?;
+
+        let job_data = sunnah_fast_job.job_data().map_err(|err| {
+            log::error!("Failed to get job data: {}", err);
+            AppErrorKind::SunnahFastReminder
+        })?;
+
+        let extra_data = serde_json::to_vec(&JobExtraData {
+            extension_type: fast.extension_type(),
+            chat_id: Some(chat_id),
         })
         .map_err(|err| {
-            log::error!("Failed to create white days message job: {}", err);
+            log::error!("Failed to serialize job extra data: {}", err);
+            AppErrorKind::SunnahFastReminder
+        })?;
+
+        sunnah_fast_job
+            .set_job_data(JobStoredData {
+                extra: extra_data,
+                ..job_data
+            })
+            .map_err(|err| {
+                log::error!("Failed to set job data: {}", err);
+                AppErrorKind::SunnahFastReminder
+            })?;
+
+        Ok(sunnah_fast_job)
+    }
+
+    /// Sends the reminder of the Sunnah fast to the user if the day before the fast is due
+    /// within the `window`, unless the user has already received it
+    async fn send_sunnah_fast_reminder(
+        &self,
+        bot: &Bot,
+        chat_id: i64,
+        fast: SunnahFast,
+        window: chrono::Duration,
+    ) {
+        let timezone = get_user_timezone(&self.pool, chat_id).await;
+        let location = get_user_location(&self.pool, chat_id).await;
+        let delivery_time = get_user_delivery_time(&self.pool, chat_id).await;
+
+        if !is_delivery_due(timezone, location, delivery_time, window) {
+            return;
+        }
+
+        if !is_notification_enabled(&self.pool, chat_id, NotificationCategory::SunnahFasts).await {
+            return;
+        }
+
+        let Some(tomorrow) = Utc::now().with_timezone(&timezone).date_naive().succ_opt() else {
+            return;
+        };
+        let hijri_date = match self.provider.get_hijri_date(tomorrow).await {
+            Ok(hijri_date) => hijri_date,
+            Err(_err) => {
+                log::error!("Failed to get the Hijri date for the Sunnah fast reminder");
+                return;
+            }
+        };
+
+        let Some(key) = fast.reminder(tomorrow, hijri_date.month_number, hijri_date.day_number)
+        else {
+            return;
+        };
+
+        let mut args = HashMap::new();
+        args.insert("day", hijri_date.day_number.to_string());
+
+        if fast == SunnahFast::Shawwal {
+            let year = hijri_date.year.parse().unwrap_or_default();
+            let count = match get_shawwal_fasts(&self.pool, chat_id, year).await {
+                Ok(count) if count < SHAWWAL_FASTS => count,
+                _ => return,
+            };
+            args.insert("count", count.to_string());
+        }
+
+        let notification =
+            Notification::new(ChatId(chat_id), vec![self.i18n.t_with_args(&key, args)]).once(
+                format!(
+                    "{}_{}",
+                    NotificationCategory::SunnahFasts.as_str(),
+                    fast.as_str()
+                ),
+                tomorrow,
+            );
+        self.dispatcher.deliver(bot, &self.pool, notification).await;
+    }
+
+    /// Stops reminding the user of the Sunnah fast
+    pub async fn remove_sunnah_fast_reminder(
+        &self,
+        chat_id: i64,
+        fast: SunnahFast,
+    ) -> Result<(), AppErrorKind> {
+        let job_ids = self
+            .user_job_ids(chat_id, fast.extension_type())
+            .await
+            .map_err(|e| {
+                log::error!("Failed to fetch the Sunnah fast reminder jobs: {}", e);
+                AppErrorKind::SunnahFastReminder
+            })?;
+
+        for job_id in job_ids {
+            self.sched.remove(&job_id).await.map_err(|err| {
+                log::error!("Failed to remove Sunnah fast reminder job: {}", err);
+                AppErrorKind::SunnahFastReminder
+            })?;
+        }
+
+        log::info!("{:?} reminder of {} removed", fast, chat_id);
+
+        Ok(())
+    }
+
+    /// Sends the white days notification to every user in the evening before the white days,
+    /// or at the delivery time the user has chosen
+    pub async fn schedule_white_days_message(&self, bot: Bot) -> Result<(), AppErrorKind> {
+        let job_with_type_exists = sqlx::query_scalar!(
+            "
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM job_extensions
+                    WHERE type = $1
+                )
+            ",
+            JobExtensionType::WhiteDaysMessage as i32,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to check for existing job: {}", e);
             AppErrorKind::WhiteDaysMessage
         })?;
 
+        if job_with_type_exists.unwrap_or(false) {
+            log::info!(
+                "Job with type {:?} already exists, skipping creation.",
+                JobExtensionType::WhiteDaysMessage
+            );
+            return Ok(());
+        }
+
+        let white_days_message_job = self.white_days_message_job(Arc::new(bot))?;
+
+        self.sched
+            .add(white_days_message_job)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to schedule white days message job: {}", err);
+                AppErrorKind::WhiteDaysMessage
+            })?;
+
+        log::info!("White days message job scheduled successfully.");
+
+        Ok(())
+    }
+
+    fn white_days_message_job(&self, bot: Arc<Bot>) -> Result<Job, AppErrorKind> {
+        let scheduler = self.clone();
+
+        // This job checks every user whose evening, or the delivery time the user has chosen,
+        // has just started, and sends a notification if the white days start tomorrow
+        let mut white_days_message_job =
+            Job::new_async(EVENING_CHECK_SCHEDULE, move |_uuid, _l| {
+                let scheduler = scheduler.clone();
+                let bot = Arc::clone(&bot);
+
+                Box::pin(async move {
+                    scheduler
+                        .send_white_days_messages(
+                            &bot,
+                            chrono::Duration::minutes(EVENING_CHECK_MINUTES),
+                        )
+                        .await;
+                })
+            })
+            .map_err(|err| {
+                log::error!("Failed to create white days message job: {}", err);
+                AppErrorKind::WhiteDaysMessage
+            })?;
+
         let job_data = white_days_message_job.job_data().map_err(|err| {
             log::error!("Failed to get job data: {}", err);
             AppErrorKind::WhiteDaysMessage
@@ -161,6 +860,7 @@ impl Scheduler {
 
         let extra_data = serde_json::to_vec(&JobExtraData {
             extension_type: JobExtensionType::WhiteDaysMessage,
+            chat_id: None,
         })
         .map_err(|err| {
             log::error!("Failed to serialize job extra data: {}", err);
@@ -177,16 +877,611 @@ impl Scheduler {
                 AppErrorKind::WhiteDaysMessage
             })?;
 
-        self.sched
-            .add(white_days_message_job.clone())
-            .await
+        Ok(white_days_message_job)
+    }
+
+    /// Sends the white days notification to the users whose evening, or delivery time, is due
+    /// within the `window`, and who haven't received it yet. The message of each date is
+    /// resolved once, as most of the users share it.
+    async fn send_white_days_messages(&self, bot: &Bot, window: chrono::Duration) {
+        let users = match sqlx::query!(
+            "
+                SELECT chat_id, timezone, latitude, longitude, delivery_time
+                FROM users
+                WHERE active AND NOT EXISTS (
+                    SELECT 1
+                    FROM notification_preferences
+                    WHERE user_id = users.id AND category = $1 AND NOT enabled
+                )
+            ",
+            NotificationCategory::WhiteDays.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(users) => users,
+            Err(e) => {
+                log::error!("Failed to fetch users for the white days message: {}", e);
+                return;
+            }
+        };
+
+        let mut messages: HashMap<NaiveDate, Option<String>> = HashMap::new();
+        let mut notifications = Vec::new();
+
+        for user in users {
+            let timezone = user.timezone.parse().unwrap_or(DEFAULT_TIMEZONE);
+            let location = match (user.latitude, user.longitude) {
+                (Some(latitude), Some(longitude)) => Location::new(latitude, longitude),
+                _ => None,
+            };
+
+            if !is_delivery_due(timezone, location, user.delivery_time, window) {
+                continue;
+            }
+
+            let Some(tomorrow) = Utc::now().with_timezone(&timezone).date_naive().succ_opt() else {
+                continue;
+            };
+
+            let message = match messages.get(&tomorrow) {
+                Some(message) => message.clone(),
+                None => {
+                    let message =
+                        white_days_message(self.provider.as_ref(), &self.i18n, tomorrow).await;
+                    messages.insert(tomorrow, message.clone());
+                    message
+                }
+            };
+
+            if let Some(message) = message {
+                notifications.push(
+                    Notification::new(ChatId(user.chat_id), vec![message])
+                        .once(NotificationCategory::WhiteDays.as_str(), tomorrow),
+                );
+            }
+        }
+
+        self.dispatcher
+            .broadcast(bot, &self.pool, notifications)
+            .await;
+    }
+
+    /// Sends the Ramadan reminders to every user while the Hijri month is Ramadan: the number
+    /// of the day with the times of its fast in the evening before it, the suhoor and iftar
+    /// reminders, the odd nights of the last ten nights and the Sadaqat al-Fitr reminder
+    pub async fn schedule_ramadan_reminders(&self, bot: Bot) -> Result<(), AppErrorKind> {
+        let job_with_type_exists = sqlx::query_scalar!(
+            "
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM job_extensions
+                    WHERE type = $1
+                )
+            ",
+            JobExtensionType::RamadanReminder as i32,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to check for existing job: {}", e);
+            AppErrorKind::RamadanReminder
+        })?;
+
+        if job_with_type_exists.unwrap_or(false) {
+            log::info!(
+                "Job with type {:?} already exists, skipping creation.",
+                JobExtensionType::RamadanReminder
+            );
+            return Ok(());
+        }
+
+        let ramadan_job = self.ramadan_job(Arc::new(bot))?;
+
+        self.sched.add(ramadan_job).await.map_err(|err| {
+            log::error!("Failed to schedule Ramadan reminder job: {}", err);
+            AppErrorKind::RamadanReminder
+        })?;
+
+        log::info!("Ramadan reminder job scheduled successfully.");
+
+        Ok(())
+    }
+
+    fn ramadan_job(&self, bot: Arc<Bot>) -> Result<Job, AppErrorKind> {
+        let provider = Arc::clone(&self.provider);
+        let pool = self.pool.clone();
+        let i18n = Arc::clone(&self.i18n);
+        let default_config = self.prayer_times_config;
+        // The suhoor and iftar reminders are due at the prayer times, so they aren't spread
+        let dispatcher = self.dispatcher.clone().without_spread();
+
+        let mut ramadan_job = Job::new_async(RAMADAN_CHECK_SCHEDULE, move |_uuid, _l| {
+            let provider = Arc::clone(&provider);
+            let pool = pool.clone();
+            let i18n = Arc::clone(&i18n);
+            let bot = Arc::clone(&bot);
+            let dispatcher = dispatcher.clone();
+
+            Box::pin(async move {
+                let now = Utc::now();
+                let default_today = now.with_timezone(&DEFAULT_TIMEZONE).date_naive();
+                let mut hijri_dates = HashMap::new();
+
+                // The day before is checked too, as the users behind the default timezone may
+                // still be in it
+                for date in [
+                    default_today.pred_opt(),
+                    Some(default_today),
+                    default_today.succ_opt(),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    match provider.get_hijri_date(date).await {
+                        Ok(hijri_date) => {
+                            hijri_dates.insert(date, hijri_date);
+                        }
+                        Err(_err) => {
+                            log::error!("Failed to get the Hijri date for the Ramadan reminders");
+                            return;
+                        }
+                    }
+                }
+
+                if !hijri_dates
+                    .values()
+                    .any(|hijri_date| hijri_date.month_number == RAMADAN)
+                {
+                    return;
+                }
+
+                let users = match sqlx::query!(
+                    "
+                        SELECT chat_id, timezone, latitude, longitude, prayer_times_method, asr_method
+                        FROM users
+                        WHERE active AND NOT EXISTS (
+                            SELECT 1
+                            FROM notification_preferences
+                            WHERE user_id = users.id AND category = $1 AND NOT enabled
+                        )
+                    ",
+                    NotificationCategory::Ramadan.as_str()
+                )
+                .fetch_all(&pool)
+                .await
+                {
+                    Ok(users) => users,
+                    Err(e) => {
+                        log::error!("Failed to fetch users for the Ramadan reminders: {}", e);
+                        return;
+                    }
+                };
+
+                let window = chrono::Duration::minutes(1);
+                let mut notifications = Vec::new();
+
+                for user in users {
+                    let timezone = user.timezone.parse().unwrap_or(DEFAULT_TIMEZONE);
+                    let location = match (user.latitude, user.longitude) {
+                        (Some(latitude), Some(longitude)) => Location::new(latitude, longitude),
+                        _ => None,
+                    };
+                    let config = PrayerTimesConfig {
+                        method: user
+                            .prayer_times_method
+                            .and_then(|method| method.parse().ok())
+                            .unwrap_or(default_config.method),
+                        asr: user
+                            .asr_method
+                            .and_then(|asr| asr.parse().ok())
+                            .unwrap_or(default_config.asr),
+                    };
+                    let prayer_times_of = |date| {
+                        location.and_then(|location| PrayerTimes::calculate(date, location, &config))
+                    };
+
+                    let today = now.with_timezone(&timezone).date_naive();
+                    let Some(tomorrow) = today.succ_opt() else {
+                        continue;
+                    };
+
+                    for date in [today, tomorrow] {
+                        if let Entry::Vacant(entry) = hijri_dates.entry(date)
+                            && let Ok(hijri_date) = provider.get_hijri_date(date).await
+                        {
+                            entry.insert(hijri_date);
+                        }
+                    }
+
+                    let (Some(today_hijri), Some(tomorrow_hijri)) =
+                        (hijri_dates.get(&today), hijri_dates.get(&tomorrow))
+                    else {
+                        log::error!("Failed to get the Hijri date for the Ramadan reminders");
+                        continue;
+                    };
+
+                    let fast_prayer_times = (today_hijri.month_number == RAMADAN)
+                        .then(|| prayer_times_of(today))
+                        .flatten();
+                    if let Some(prayer_times) = fast_prayer_times {
+                        for reminder in FastReminder::due(&prayer_times, now, window) {
+                            notifications.push(
+                                Notification::new(
+                                    ChatId(user.chat_id),
+                                    vec![reminder.message(&prayer_times, timezone, &i18n)],
+                                )
+                                .once(
+                                    format!(
+                                        "{}_{}",
+                                        NotificationCategory::Ramadan.as_str(),
+                                        reminder.as_str()
+                                    ),
+                                    today,
+                                ),
+                            );
+                        }
+                    }
+
+                    if tomorrow_hijri.month_number == RAMADAN
+                        && is_evening_due(timezone, location, window)
+                    {
+                        notifications.push(
+                            Notification::new(
+                                ChatId(user.chat_id),
+                                evening_messages(
+                                    tomorrow_hijri.day_number,
+                                    prayer_times_of(tomorrow).as_ref(),
+                                    timezone,
+                                    &i18n,
+                                ),
+                            )
+                            .once(NotificationCategory::Ramadan.as_str(), tomorrow),
+                        );
+                    }
+                }
+
+                dispatcher.broadcast(&bot, &pool, notifications).await;
+            })
+        })
+        .map_err(|err| {
+            log::error!("Failed to create Ramadan reminder job: {}", err);
+            AppErrorKind::RamadanReminder
+        })?;
+
+        let job_data = ramadan_job.job_data().map_err(|err| {
+            log::error!("Failed to get job data: {}", err);
+            AppErrorKind::RamadanReminder
+        })?;
+
+        let extra_data = serde_json::to_vec(&JobExtraData {
+            extension_type: JobExtensionType::RamadanReminder,
+            chat_id: None,
+        })
+        .map_err(|err| {
+            log::error!("Failed to serialize job extra data: {}", err);
+            AppErrorKind::RamadanReminder
+        })?;
+
+        ramadan_job
+            .set_job_data(JobStoredData {
+                extra: extra_data,
+                ..job_data
+            })
             .map_err(|err| {
-                log::error!("Failed to schedule white days message job: {}", err);
-                AppErrorKind::WhiteDaysMessage
+                log::error!("Failed to set job data: {}", err);
+                AppErrorKind::RamadanReminder
             })?;
 
-        log::info!("White days message job scheduled successfully.");
+        Ok(ramadan_job)
+    }
+
+    /// Announces the events of the catalog to every user in the evenings at their lead times,
+    /// as the Hijri day of an event starts in the evening before it
+    pub async fn schedule_event_reminders(
+        &self,
+        bot: Bot,
+        events: Arc<EventCatalog>,
+    ) -> Result<(), AppErrorKind> {
+        let job_with_type_exists = sqlx::query_scalar!(
+            "
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM job_extensions
+                    WHERE type = $1
+                )
+            ",
+            JobExtensionType::EventReminder as i32,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to check for existing job: {}", e);
+            AppErrorKind::EventReminder
+        })?;
+
+        if job_with_type_exists.unwrap_or(false) {
+            log::info!(
+                "Job with type {:?} already exists, skipping creation.",
+                JobExtensionType::EventReminder
+            );
+            return Ok(());
+        }
+
+        let event_job = self.event_reminder_job(Arc::new(bot), events)?;
+
+        self.sched.add(event_job).await.map_err(|err| {
+            log::error!("Failed to schedule event reminder job: {}", err);
+            AppErrorKind::EventReminder
+        })?;
+
+        log::info!("Event reminder job scheduled successfully.");
 
         Ok(())
     }
+
+    fn event_reminder_job(
+        &self,
+        bot: Arc<Bot>,
+        events: Arc<EventCatalog>,
+    ) -> Result<Job, AppErrorKind> {
+        let scheduler = self.clone();
+
+        let mut event_job = Job::new_async(EVENING_CHECK_SCHEDULE, move |_uuid, _l| {
+            let scheduler = scheduler.clone();
+            let bot = Arc::clone(&bot);
+            let events = Arc::clone(&events);
+
+            Box::pin(async move {
+                scheduler
+                    .send_event_reminders(
+                        &bot,
+                        &events,
+                        chrono::Duration::minutes(EVENING_CHECK_MINUTES),
+                    )
+                    .await;
+            })
+        })
+        .map_err(|err| {
+            log::error!("Failed to create event reminder job: {}", err);
+            AppErrorKind::EventReminder
+        })?;
+
+        let job_data = event_job.job_data().map_err(|err| {
+            log::error!("Failed to get job data: {}", err);
+            AppErrorKind::EventReminder
+        })?;
+
+        let extra_data = serde_json::to_vec(&JobExtraData {
+            extension_type: JobExtensionType::EventReminder,
+            chat_id: None,
+        })
+        .map_err(|err| {
+            log::error!("Failed to serialize job extra data: {}", err);
+            AppErrorKind::EventReminder
+        })?;
+
+        event_job
+            .set_job_data(JobStoredData {
+                extra: extra_data,
+                ..job_data
+            })
+            .map_err(|err| {
+                log::error!("Failed to set job data: {}", err);
+                AppErrorKind::EventReminder
+            })?;
+
+        Ok(event_job)
+    }
+
+    /// Announces the events to the users whose evening is due within the `window`, at the lead
+    /// times of the events, unless the users have already received the announcements
+    async fn send_event_reminders(
+        &self,
+        bot: &Bot,
+        events: &EventCatalog,
+        window: chrono::Duration,
+    ) {
+        let users = match sqlx::query!(
+            "
+                SELECT chat_id, timezone, latitude, longitude
+                FROM users
+                WHERE active AND NOT EXISTS (
+                    SELECT 1
+                    FROM notification_preferences
+                    WHERE user_id = users.id AND category = $1 AND NOT enabled
+                )
+            ",
+            NotificationCategory::Events.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(users) => users,
+            Err(e) => {
+                log::error!("Failed to fetch users for the event reminders: {}", e);
+                return;
+            }
+        };
+
+        let lead_days = events.lead_days();
+        let mut notifications = Vec::new();
+
+        for user in users {
+            let timezone = user.timezone.parse().unwrap_or(DEFAULT_TIMEZONE);
+            let location = match (user.latitude, user.longitude) {
+                (Some(latitude), Some(longitude)) => Location::new(latitude, longitude),
+                _ => None,
+            };
+
+            if !is_evening_due(timezone, location, window) {
+                continue;
+            }
+
+            let Some(tomorrow) = Utc::now().with_timezone(&timezone).date_naive().succ_opt() else {
+                continue;
+            };
+            for days in &lead_days {
+                let date = tomorrow + chrono::Duration::days(*days as i64);
+                let hijri_date = match self.provider.get_hijri_date(date).await {
+                    Ok(hijri_date) => hijri_date,
+                    Err(_err) => {
+                        log::error!("Failed to get the Hijri date for the event reminders");
+                        continue;
+                    }
+                };
+
+                for event in events.announced(hijri_date.month_number, hijri_date.day_number, *days)
+                {
+                    notifications.push(
+                        Notification::new(
+                            ChatId(user.chat_id),
+                            vec![event.announcement(*days, date, &self.i18n)],
+                        )
+                        .once(
+                            format!("{}_{}", NotificationCategory::Events.as_str(), event.id),
+                            tomorrow,
+                        ),
+                    );
+                }
+            }
+        }
+
+        self.dispatcher
+            .broadcast(bot, &self.pool, notifications)
+            .await;
+    }
+}
+
+/// Notification to send in the evening before the Gregorian `tomorrow`, if it is the first of
+/// the white days on which fasting is allowed: none in Ramadan and the 14th in Dhul-Hijjah
+async fn white_days_message(
+    provider: &dyn HijriDateProvider,
+    i18n: &I18n,
+    tomorrow: NaiveDate,
+) -> Option<String> {
+    let date_response = match provider.get_hijri_date(tomorrow).await {
+        Ok(date_response) => date_response,
+        Err(_err) => {
+            log::error!("Hijri date fetch error for the white days message");
+            return None;
+        }
+    };
+
+    let day = date_response.day_number;
+    if !WHITE_DAYS.contains(&day) {
+        return None;
+    }
+
+    let thirteenth = tomorrow - chrono::Duration::days((day - WHITE_DAYS[0]) as i64);
+    let days = white_days(date_response.month_number, thirteenth);
+    if days.first() != Some(&day) {
+        log::info!(
+            "Tomorrow's Hijri date is {}, not sending white days message.",
+            date_response
+        );
+        return None;
+    }
+
+    let mut args = HashMap::new();
+    args.insert("month", date_response.month_name);
+
+    if days == WHITE_DAYS {
+        return Some(i18n.t_with_args(&TranslationKey::WhiteDaysNotification, args));
+    }
+
+    args.insert(
+        "days",
+        days.iter()
+            .map(|day| format!("{}.", day))
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    Some(i18n.t_with_args(&TranslationKey::WhiteDaysNotificationShortened, args))
+}
+
+/// Deletes the jobs of the user with their extensions and notifications, along with the prayer
+/// reminders, notification settings, Shawwal fasts, delivery time and deliveries of the user, and
+/// marks the user inactive. Returns the deleted jobs, which the running scheduler still holds
+pub async fn delete_user_data(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let job_ids = sqlx::query_scalar!(
+        "
+            SELECT users_jobs.job_id
+            FROM users_jobs
+            JOIN users ON users.id = users_jobs.user_id
+            WHERE users.chat_id = $1
+        ",
+        chat_id,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM notifications WHERE job_id = ANY($1)", &job_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    // The extensions and the users of the jobs are deleted along with them
+    sqlx::query!("DELETE FROM jobs WHERE id = ANY($1)", &job_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!(
+        "
+            DELETE FROM prayer_reminders
+            USING users
+            WHERE users.id = prayer_reminders.user_id AND users.chat_id = $1
+        ",
+        chat_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "
+            DELETE FROM notification_preferences
+            USING users
+            WHERE users.id = notification_preferences.user_id AND users.chat_id = $1
+        ",
+        chat_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "
+            DELETE FROM shawwal_fasts
+            USING users
+            WHERE users.id = shawwal_fasts.user_id AND users.chat_id = $1
+        ",
+        chat_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "
+            DELETE FROM deliveries
+            USING users
+            WHERE users.id = deliveries.user_id AND users.chat_id = $1
+        ",
+        chat_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE users SET active = FALSE, delivery_time = NULL WHERE chat_id = $1",
+        chat_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(job_ids)
 }
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+
+use crate::location::Location;
+
+/// Altitude of the sun's centre at the visible sunrise and sunset, accounting for the
+/// atmospheric refraction and the radius of the sun
+const SUNSET_ALTITUDE: f64 = -0.833;
+
+/// Julian day of 0001-01-01 at midnight UTC, minus one day
+const JULIAN_DAY_OFFSET: f64 = 1_721_424.5;
+
+/// Julian day of the J2000.0 epoch
+const J2000: f64 = 2_451_545.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SunDirection {
+    Rising,
+    Setting,
+}
+
+pub struct SunPosition {
+    /// In degrees
+    pub declination: f64,
+    /// In hours
+    pub equation_of_time: f64,
+}
+
+/// Julian day at midnight UTC of the date
+pub fn julian_day(date: NaiveDate) -> f64 {
+    date.num_days_from_ce() as f64 + JULIAN_DAY_OFFSET
+}
+
+/// Low precision position of the sun, accurate to about a minute of time
+/// between 1950 and 2050 (see the Astronomical Almanac)
+pub fn sun_position(julian_day: f64) -> SunPosition {
+    let days = julian_day - J2000;
+    let mean_anomaly = (357.529 + 0.985_600_28 * days).to_radians();
+    let mean_longitude = (280.459 + 0.985_647_36 * days).rem_euclid(360.0);
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin())
+            .to_radians();
+    let obliquity = (23.439 - 0.000_000_36 * days).to_radians();
+
+    let right_ascension = (obliquity.cos() * ecliptic_longitude.sin())
+        .atan2(ecliptic_longitude.cos())
+        .to_degrees()
+        .rem_euclid(360.0)
+        / 15.0;
+    let declination = (obliquity.sin() * ecliptic_longitude.sin())
+        .asin()
+        .to_degrees();
+    let equation_of_time = mean_longitude / 15.0 - right_ascension;
+
+    SunPosition {
+        declination,
+        // Keep it within ±12 hours, as the longitude and the right ascension can be on
+        // different sides of 0°
+        equation_of_time: (equation_of_time + 12.0).rem_euclid(24.0) - 12.0,
+    }
+}
+
+/// Hours after midnight UTC at which the sun crosses the meridian
+fn transit(position: &SunPosition, longitude: f64) -> f64 {
+    12.0 - longitude / 15.0 - position.equation_of_time
+}
+
+/// Hours between the transit and the moment the sun reaches the altitude, if it reaches it
+fn hour_angle(altitude: f64, latitude: f64, declination: f64) -> Option<f64> {
+    let (latitude, declination) = (latitude.to_radians(), declination.to_radians());
+    let cos_hour_angle = (altitude.to_radians().sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+
+    (-1.0..=1.0)
+        .contains(&cos_hour_angle)
+        .then(|| cos_hour_angle.acos().to_degrees() / 15.0)
+}
+
+fn to_utc(date: NaiveDate, hours: f64) -> Option<DateTime<Utc>> {
+    let midnight = date.and_hms_opt(0, 0, 0)?.and_utc();
+
+    midnight.checked_add_signed(chrono::Duration::milliseconds(
+        (hours * 3_600_000.0).round() as i64,
+    ))
+}
+
+/// Moment of the local day at which the sun reaches the altitude (in degrees), or `None` when
+/// it stays above or below it all day, as it happens close to the poles
+pub fn time_at_altitude(
+    date: NaiveDate,
+    location: Location,
+    altitude: f64,
+    direction: SunDirection,
+) -> Option<DateTime<Utc>> {
+    let mut hours = 12.0 - location.longitude / 15.0;
+
+    // The second pass uses the position of the sun at the first estimate
+    for _ in 0..2 {
+        let position = sun_position(julian_day(date) + hours / 24.0);
+        let hour_angle = hour_angle(altitude, location.latitude, position.declination)?;
+
+        hours = match direction {
+            SunDirection::Rising => transit(&position, location.longitude) - hour_angle,
+            SunDirection::Setting => transit(&position, location.longitude) + hour_angle,
+        };
+    }
+
+    to_utc(date, hours)
+}
+
+/// Moment at which the sun crosses the meridian of the location
+pub fn solar_noon(date: NaiveDate, location: Location) -> Option<DateTime<Utc>> {
+    let estimate = 12.0 - location.longitude / 15.0;
+    let position = sun_position(julian_day(date) + estimate / 24.0);
+
+    to_utc(date, transit(&position, location.longitude))
+}
+
+pub fn sunrise(date: NaiveDate, location: Location) -> Option<DateTime<Utc>> {
+    time_at_altitude(date, location, SUNSET_ALTITUDE, SunDirection::Rising)
+}
+
+pub fn sunset(date: NaiveDate, location: Location) -> Option<DateTime<Utc>> {
+    time_at_altitude(date, location, SUNSET_ALTITUDE, SunDirection::Setting)
+}
@@ -0,0 +1,175 @@
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    error::AppErrorKind,
+    fasting::{FastingRuling, FastingStatus},
+    i18n::translation_key::TranslationKey,
+    job::JobExtensionType,
+};
+
+/// Number of the days of Shawwal recommended to fast after Ramadan
+pub const SHAWWAL_FASTS: i32 = 6;
+
+const SHAWWAL: u8 = 10;
+const DHU_AL_HIJJAH: u8 = 12;
+
+/// Recommended fasts the users can opt in to be reminded of, in the evening before the day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SunnahFast {
+    MondayThursday,
+    /// Tasu'a and Ashura, the 9th and the 10th of Muharram
+    Ashura,
+    Arafah,
+    /// The first nine days of Dhul-Hijjah
+    DhulHijjah,
+    /// Six days of Shawwal, counted by the user with `/sevval`
+    Shawwal,
+}
+
+impl SunnahFast {
+    pub const ALL: [SunnahFast; 5] = [
+        SunnahFast::MondayThursday,
+        SunnahFast::Ashura,
+        SunnahFast::Arafah,
+        SunnahFast::DhulHijjah,
+        SunnahFast::Shawwal,
+    ];
+
+    pub fn extension_type(&self) -> JobExtensionType {
+        match self {
+            SunnahFast::MondayThursday => JobExtensionType::MondayThursdayFast,
+            SunnahFast::Ashura => JobExtensionType::AshuraFast,
+            SunnahFast::Arafah => JobExtensionType::ArafahFast,
+            SunnahFast::DhulHijjah => JobExtensionType::DhulHijjahFast,
+            SunnahFast::Shawwal => JobExtensionType::ShawwalFast,
+        }
+    }
+
+    pub fn from_extension_type(extension_type: i32) -> Option<Self> {
+        SunnahFast::ALL
+            .into_iter()
+            .find(|fast| fast.extension_type() as i32 == extension_type)
+    }
+
+    /// Keyword of the fast in the commands
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SunnahFast::MondayThursday => "ponedjeljak",
+            SunnahFast::Ashura => "ašura",
+            SunnahFast::Arafah => "arefat",
+            SunnahFast::DhulHijjah => "zulhidždže",
+            SunnahFast::Shawwal => "ševval",
+        }
+    }
+
+    pub fn translation_key(&self) -> TranslationKey {
+        match self {
+            SunnahFast::MondayThursday => TranslationKey::SunnahFastMondayThursday,
+            SunnahFast::Ashura => TranslationKey::SunnahFastAshura,
+            SunnahFast::Arafah => TranslationKey::SunnahFastArafah,
+            SunnahFast::DhulHijjah => TranslationKey::SunnahFastDhulHijjah,
+            SunnahFast::Shawwal => TranslationKey::SunnahFastShawwal,
+        }
+    }
+
+    /// Reminder to send in the evening before the Gregorian `date`, which is the `day` of the
+    /// Hijri `month`. Nothing is sent for the days on which fasting isn't recommended.
+    pub fn reminder(&self, date: NaiveDate, month: u8, day: u8) -> Option<TranslationKey> {
+        if FastingRuling::of(date, month, day).status != FastingStatus::Recommended {
+            return None;
+        }
+
+        match (self, month, day) {
+            (SunnahFast::MondayThursday, _, _) => match date.weekday() {
+                Weekday::Mon => Some(TranslationKey::SunnahFastReminderMonday),
+                Weekday::Thu => Some(TranslationKey::SunnahFastReminderThursday),
+                _ => None,
+            },
+            (SunnahFast::Ashura, 1, 9) => Some(TranslationKey::SunnahFastReminderTasua),
+            (SunnahFast::Ashura, 1, 10) => Some(TranslationKey::SunnahFastReminderAshura),
+            (SunnahFast::Arafah, DHU_AL_HIJJAH, 9) => {
+                Some(TranslationKey::SunnahFastReminderArafah)
+            }
+            (SunnahFast::DhulHijjah, DHU_AL_HIJJAH, 1..=9) => {
+                Some(TranslationKey::SunnahFastReminderDhulHijjah)
+            }
+            (SunnahFast::Shawwal, SHAWWAL, 2..) => Some(TranslationKey::SunnahFastReminderShawwal),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for SunnahFast {
+    type Err = AppErrorKind;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "ponedjeljak" | "četvrtak" | "cetvrtak" | "monday" | "thursday" | "monday_thursday" => {
+                Ok(SunnahFast::MondayThursday)
+            }
+            "ašura" | "asura" | "tasua" | "ashura" => Ok(SunnahFast::Ashura),
+            "arefat" | "arafah" => Ok(SunnahFast::Arafah),
+            "zulhidždže" | "zulhidzdze" | "dhul_hijjah" => Ok(SunnahFast::DhulHijjah),
+            "ševval" | "sevval" | "shawwal" => Ok(SunnahFast::Shawwal),
+            _ => Err(AppErrorKind::SunnahFastReminder),
+        }
+    }
+}
+
+/// Number of the days of Shawwal of the Hijri year the user has fasted
+pub async fn get_shawwal_fasts(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    year: i32,
+) -> Result<i32, AppErrorKind> {
+    let days = sqlx::query_scalar!(
+        "
+            SELECT shawwal_fasts.days
+            FROM shawwal_fasts
+            JOIN users ON users.id = shawwal_fasts.user_id
+            WHERE users.chat_id = $1 AND shawwal_fasts.year = $2
+        ",
+        chat_id,
+        year
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to fetch the Shawwal fasts of {}: {}", chat_id, e);
+        AppErrorKind::ShawwalFasts
+    })?;
+
+    Ok(days.unwrap_or(0))
+}
+
+/// Adds the number of days to the Shawwal fasts of the user, keeping it between 0 and
+/// [`SHAWWAL_FASTS`], and returns the new number
+pub async fn add_shawwal_fasts(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    year: i32,
+    days: i32,
+) -> Result<i32, AppErrorKind> {
+    sqlx::query_scalar!(
+        "
+            INSERT INTO shawwal_fasts (user_id, year, days)
+            SELECT id, $2, GREATEST(0, LEAST($4::INTEGER, $3::INTEGER)) FROM users WHERE chat_id = $1
+            ON CONFLICT (user_id, year)
+            DO UPDATE SET days = GREATEST(0, LEAST($4::INTEGER, shawwal_fasts.days + $3::INTEGER))
+            RETURNING days
+        ",
+        chat_id,
+        year,
+        days,
+        SHAWWAL_FASTS
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to count the Shawwal fast of {}: {}", chat_id, e);
+        AppErrorKind::ShawwalFasts
+    })
+}
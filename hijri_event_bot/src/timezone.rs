@@ -0,0 +1,97 @@
+use sqlx::{Pool, Postgres, types::Uuid};
+
+use crate::{calendar::DEFAULT_TIMEZONE, error::AppErrorKind};
+
+/// Finds the IANA timezones matching the query, e.g. `America/Chicago`, `chicago` or `new york`.
+/// A full name or a city name matching exactly returns only the exact matches.
+pub fn search_timezones(query: &str) -> Vec<chrono_tz::Tz> {
+    let query = query.trim().to_lowercase().replace([' ', '-'], "_");
+
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let lowercase_name = |timezone: &chrono_tz::Tz| timezone.name().to_lowercase();
+
+    if let Some(timezone) = chrono_tz::TZ_VARIANTS
+        .iter()
+        .find(|timezone| lowercase_name(timezone) == query)
+    {
+        return vec![*timezone];
+    }
+
+    let cities: Vec<chrono_tz::Tz> = chrono_tz::TZ_VARIANTS
+        .iter()
+        .filter(|timezone| lowercase_name(timezone).rsplit('/').next() == Some(query.as_str()))
+        .copied()
+        .collect();
+
+    if !cities.is_empty() {
+        return cities;
+    }
+
+    let mut matches: Vec<chrono_tz::Tz> = chrono_tz::TZ_VARIANTS
+        .iter()
+        .filter(|timezone| lowercase_name(timezone).contains(&query))
+        .copied()
+        .collect();
+
+    // Region/City names first, the legacy aliases such as `US/Central` or `EST` last
+    matches.sort_by_key(|timezone| {
+        let name = timezone.name();
+        (
+            !name.contains('/') || name.starts_with("Etc/") || name.starts_with("US/"),
+            name.len(),
+        )
+    });
+
+    matches
+}
+
+pub async fn get_user_timezone(pool: &Pool<Postgres>, chat_id: i64) -> chrono_tz::Tz {
+    let timezone = sqlx::query_scalar!("SELECT timezone FROM users WHERE chat_id = $1", chat_id)
+        .fetch_optional(pool)
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("Failed to fetch the timezone of {}: {}", chat_id, e);
+            None
+        });
+
+    match timezone {
+        Some(timezone) => timezone.parse().unwrap_or_else(|_e| {
+            log::warn!("Unknown timezone {} of {}", timezone, chat_id);
+            DEFAULT_TIMEZONE
+        }),
+        None => DEFAULT_TIMEZONE,
+    }
+}
+
+pub async fn set_user_timezone(
+    pool: &Pool<Postgres>,
+    chat_id: i64,
+    username: Option<String>,
+    timezone: chrono_tz::Tz,
+) -> Result<(), AppErrorKind> {
+    sqlx::query!(
+        "
+            INSERT INTO users (id, chat_id, username, timezone)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (chat_id)
+            DO UPDATE SET timezone = EXCLUDED.timezone
+        ",
+        Uuid::new_v4(),
+        chat_id,
+        username,
+        timezone.name()
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Failed to set the timezone of {}: {}", chat_id, e);
+        AppErrorKind::UserTimezone
+    })?;
+
+    log::info!("Timezone of {} set to {}", chat_id, timezone);
+
+    Ok(())
+}
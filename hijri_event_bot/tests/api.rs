@@ -0,0 +1,305 @@
+use std::time::Duration;
+
+use hijri_event_bot::{
+    api::{CurrentDateResponse, HijriApi, HijriApiResponse},
+    calendar::{DEFAULT_TIMEZONE, HijriYmd},
+    error::AppErrorKind,
+    http::{HttpClient, HttpClientConfig},
+    i18n::translation_key::TranslationKey,
+    provider::HijriDateProvider,
+};
+
+use crate::support::{MockAladhanServer, MockResponse, date, fixture, i18n};
+
+mod support;
+
+fn test_config() -> HttpClientConfig {
+    HttpClientConfig {
+        timeout: Duration::from_millis(300),
+        connect_timeout: Duration::from_millis(300),
+        max_retries: 2,
+        retry_base_delay: Duration::from_millis(1),
+        retry_max_delay: Duration::from_millis(5),
+        failure_threshold: 100,
+        ..HttpClientConfig::default()
+    }
+}
+
+fn api_with_config(server: &MockAladhanServer, config: HttpClientConfig) -> HijriApi {
+    HijriApi::new(i18n(), HttpClient::new(config).unwrap()).with_base_url(server.base_url())
+}
+
+fn api(server: &MockAladhanServer) -> HijriApi {
+    api_with_config(server, test_config())
+}
+
+#[tokio::test]
+async fn fetches_the_hijri_date() {
+    let server = MockAladhanServer::start().await;
+    server.respond("/gToH/18-02-2026", MockResponse::fixture("gToH_18-02-2026"));
+
+    let response = api(&server)
+        .fetch_hijri_date(date(2026, 2, 18))
+        .await
+        .unwrap();
+
+    assert_eq!(response.day, "01");
+    assert_eq!(response.day_number, 1);
+    assert_eq!(response.month, "09");
+    assert_eq!(response.month_number, 9);
+    assert_eq!(response.year, "1447");
+    assert_eq!(response.month_name, "Ramazan");
+    assert_eq!(response.month_ar, "رَمَضان");
+    assert!(response.is_plausible());
+    assert_eq!(server.requests(), vec!["/gToH/18-02-2026"]);
+}
+
+#[tokio::test]
+async fn fetches_the_current_hijri_date_for_today() {
+    let server = MockAladhanServer::start().await;
+    server.respond("/gToH/", MockResponse::fixture("gToH_27-05-2026"));
+
+    let response = api(&server).get_current_hijri_date().await.unwrap();
+    let today = chrono::Utc::now()
+        .with_timezone(&DEFAULT_TIMEZONE)
+        .date_naive();
+
+    assert_eq!(response.to_string(), "1447-12-10 (Zu-l-hidždže)");
+    assert_eq!(
+        server.requests(),
+        vec![format!("/gToH/{}", today.format("%d-%m-%Y"))]
+    );
+}
+
+#[tokio::test]
+async fn fetches_the_gregorian_date() {
+    let server = MockAladhanServer::start().await;
+    server.respond("/hToG/01-10-1447", MockResponse::fixture("hToG_01-10-1447"));
+
+    let gregorian_date = api(&server)
+        .fetch_gregorian_date(&HijriYmd {
+            year: 1447,
+            month: 10,
+            day: 1,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(gregorian_date, date(2026, 3, 20));
+}
+
+#[test]
+fn maps_the_recorded_response() {
+    let hijri_data = serde_json::from_str::<HijriApiResponse>(&fixture("gToH_27-05-2026")).unwrap();
+
+    let response = CurrentDateResponse::new(hijri_data, &i18n());
+
+    assert_eq!(response.day, "10");
+    assert_eq!(response.month, "12");
+    assert_eq!(response.month_ar, "ذوالحجة");
+}
+
+#[test]
+fn pads_the_day_and_month() {
+    let hijri_data = serde_json::from_value::<HijriApiResponse>(serde_json::json!({
+        "data": { "hijri": { "day": "5", "month": { "number": 3, "ar": "" }, "year": "1448" } }
+    }))
+    .unwrap();
+
+    let response = CurrentDateResponse::new(hijri_data, &i18n());
+
+    assert_eq!(response.day, "05");
+    assert_eq!(response.day_number, 5);
+    assert_eq!(response.month, "03");
+}
+
+#[test]
+fn maps_the_month_names() {
+    let i18n = i18n();
+    let months = [
+        (1, TranslationKey::MonthMuharram),
+        (2, TranslationKey::MonthSafar),
+        (3, TranslationKey::MonthRabiAlAwwal),
+        (4, TranslationKey::MonthRabiAlThani),
+        (5, TranslationKey::MonthJumadaAlAwwal),
+        (6, TranslationKey::MonthJumadaAlThani),
+        (7, TranslationKey::MonthRajab),
+        (8, TranslationKey::MonthShaaban),
+        (9, TranslationKey::MonthRamadan),
+        (10, TranslationKey::MonthShawwal),
+        (11, TranslationKey::MonthDhuAlQiDah),
+        (12, TranslationKey::MonthDhuAlHijjah),
+        (13, TranslationKey::MonthUnknown),
+    ];
+
+    for (month, key) in months {
+        let hijri_data = serde_json::from_value::<HijriApiResponse>(serde_json::json!({
+            "data": { "hijri": { "day": "01", "month": { "number": month, "ar": "" }, "year": "1447" } }
+        }))
+        .unwrap();
+
+        let response = CurrentDateResponse::new(hijri_data, &i18n);
+
+        assert_eq!(response.month_name, i18n.t(&key), "month {}", month);
+        assert_ne!(response.month_name, <&str>::from(&key), "month {}", month);
+    }
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() {
+    let server = MockAladhanServer::start().await;
+    server.respond("/gToH/", MockResponse::new(400, &fixture("bad_request")));
+
+    let result = api(&server).fetch_hijri_date(date(2026, 2, 18)).await;
+
+    assert!(matches!(result, Err(AppErrorKind::ApiClientError)));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn retries_server_errors() {
+    let server = MockAladhanServer::start().await;
+    server
+        .respond("/gToH/", MockResponse::status(503))
+        .respond("/gToH/", MockResponse::status(429))
+        .respond("/gToH/", MockResponse::fixture("gToH_18-02-2026"));
+
+    let response = api(&server)
+        .fetch_hijri_date(date(2026, 2, 18))
+        .await
+        .unwrap();
+
+    assert_eq!(response.month_number, 9);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_the_last_retry() {
+    let server = MockAladhanServer::start().await;
+    server.respond("/gToH/", MockResponse::status(500));
+
+    let result = api(&server).fetch_hijri_date(date(2026, 2, 18)).await;
+
+    assert!(matches!(result, Err(AppErrorKind::ApiUnavailable)));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn times_out_slow_responses() {
+    let server = MockAladhanServer::start().await;
+    server.respond(
+        "/gToH/",
+        MockResponse::fixture("gToH_18-02-2026").with_delay(Duration::from_secs(2)),
+    );
+
+    let result = api_with_config(
+        &server,
+        HttpClientConfig {
+            max_retries: 0,
+            ..test_config()
+        },
+    )
+    .fetch_hijri_date(date(2026, 2, 18))
+    .await;
+
+    assert!(matches!(result, Err(AppErrorKind::ApiTimeout)));
+}
+
+#[tokio::test]
+async fn rejects_malformed_payloads() {
+    let server = MockAladhanServer::start().await;
+    server
+        .respond("/gToH/18-02-2026", MockResponse::fixture("bad_request"))
+        .respond(
+            "/gToH/19-02-2026",
+            MockResponse::new(200, r#"{"code":200,"status":"OK","data":{"hijri":{"day":"#),
+        )
+        .respond("/gToH/20-02-2026", MockResponse::new(200, "<html></html>"));
+    let api = api(&server);
+
+    for day in 18..=20 {
+        let result = api.fetch_hijri_date(date(2026, 2, day)).await;
+
+        assert!(
+            matches!(result, Err(AppErrorKind::ApiInvalidResponse)),
+            "day {}",
+            day
+        );
+    }
+
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn rejects_malformed_gregorian_dates() {
+    let server = MockAladhanServer::start().await;
+    server.respond(
+        "/hToG/",
+        MockResponse::new(200, r#"{"data":{"gregorian":{"date":"2026-03-20"}}}"#),
+    );
+
+    let result = api(&server)
+        .fetch_gregorian_date(&HijriYmd {
+            year: 1447,
+            month: 10,
+            day: 1,
+        })
+        .await;
+
+    assert!(matches!(result, Err(AppErrorKind::ApiInvalidResponse)));
+}
+
+#[tokio::test]
+async fn stops_the_requests_after_repeated_failures() {
+    let server = MockAladhanServer::start().await;
+    server.respond("/gToH/", MockResponse::status(502));
+    let api = api_with_config(
+        &server,
+        HttpClientConfig {
+            max_retries: 0,
+            failure_threshold: 2,
+            ..test_config()
+        },
+    );
+
+    for _ in 0..2 {
+        let result = api.fetch_hijri_date(date(2026, 2, 18)).await;
+        assert!(matches!(result, Err(AppErrorKind::ApiUnavailable)));
+    }
+
+    let result = api.fetch_hijri_date(date(2026, 2, 18)).await;
+
+    assert!(matches!(result, Err(AppErrorKind::ApiCircuitOpen)));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn closes_the_circuit_when_the_trial_request_is_rejected() {
+    let server = MockAladhanServer::start().await;
+    server
+        .respond("/gToH/", MockResponse::status(502))
+        .respond("/gToH/", MockResponse::status(400))
+        .respond("/gToH/", MockResponse::fixture("gToH_18-02-2026"));
+    let api = api_with_config(
+        &server,
+        HttpClientConfig {
+            max_retries: 0,
+            failure_threshold: 1,
+            open_circuit_duration: Duration::from_millis(50),
+            ..test_config()
+        },
+    );
+
+    let result = api.fetch_hijri_date(date(2026, 2, 18)).await;
+    assert!(matches!(result, Err(AppErrorKind::ApiUnavailable)));
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    let result = api.fetch_hijri_date(date(2026, 2, 18)).await;
+    assert!(matches!(result, Err(AppErrorKind::ApiClientError)));
+
+    let result = api.fetch_hijri_date(date(2026, 2, 18)).await;
+
+    assert!(result.is_ok());
+    assert_eq!(server.requests().len(), 3);
+}
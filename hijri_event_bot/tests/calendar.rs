@@ -0,0 +1,80 @@
+use chrono::NaiveDate;
+use hijri_event_bot::calendar::{CalendarMethod, HijriCalendar, HijriYmd};
+
+use crate::support::{date, i18n};
+
+mod support;
+
+fn calendar(method: CalendarMethod) -> HijriCalendar {
+    HijriCalendar::new(method, i18n())
+}
+
+fn hijri(year: i32, month: u8, day: u8) -> HijriYmd {
+    HijriYmd { year, month, day }
+}
+
+#[test]
+fn converts_known_umm_al_qura_dates() {
+    let calendar = calendar(CalendarMethod::UmmAlQura);
+
+    for (gregorian, hijri_date) in [
+        (date(2024, 3, 11), hijri(1445, 9, 1)),
+        (date(2024, 4, 10), hijri(1445, 10, 1)),
+        (date(2024, 7, 7), hijri(1446, 1, 1)),
+        (date(2026, 2, 18), hijri(1447, 9, 1)),
+    ] {
+        assert_eq!(calendar.to_hijri(gregorian).unwrap(), hijri_date);
+        assert_eq!(calendar.to_gregorian(&hijri_date).unwrap(), gregorian);
+    }
+}
+
+#[test]
+fn converts_known_tabular_dates() {
+    let calendar = calendar(CalendarMethod::Tabular);
+
+    for (gregorian, hijri_date) in [
+        // 16 July 622 in the Julian calendar
+        (date(622, 7, 19), hijri(1, 1, 1)),
+        (date(2024, 7, 8), hijri(1446, 1, 1)),
+    ] {
+        assert_eq!(calendar.to_hijri(gregorian).unwrap(), hijri_date);
+        assert_eq!(calendar.to_gregorian(&hijri_date).unwrap(), gregorian);
+    }
+}
+
+#[test]
+fn follows_the_tabular_leap_years() {
+    let calendar = calendar(CalendarMethod::Tabular);
+
+    assert_eq!(calendar.days_in_month(1, 12), Some(29));
+    assert_eq!(calendar.days_in_month(2, 12), Some(30));
+    assert_eq!(calendar.days_in_month(1447, 1), Some(30));
+    assert_eq!(calendar.days_in_month(1447, 2), Some(29));
+    assert_eq!(calendar.days_in_month(1447, 13), None);
+}
+
+#[test]
+fn falls_back_to_the_tabular_calendar_outside_the_umm_al_qura_table() {
+    let umm_al_qura = calendar(CalendarMethod::UmmAlQura);
+    let tabular = calendar(CalendarMethod::Tabular);
+
+    for gregorian in [date(1800, 1, 1), date(2200, 1, 1)] {
+        assert_eq!(
+            umm_al_qura.to_hijri(gregorian).unwrap(),
+            tabular.to_hijri(gregorian).unwrap()
+        );
+    }
+}
+
+#[test]
+fn rejects_the_dates_out_of_range() {
+    for method in [CalendarMethod::UmmAlQura, CalendarMethod::Tabular] {
+        let calendar = calendar(method);
+
+        assert!(calendar.to_hijri(date(622, 7, 18)).is_err());
+        assert!(calendar.to_hijri(NaiveDate::MAX).is_err());
+        assert!(calendar.to_gregorian(&hijri(0, 1, 1)).is_err());
+        assert!(calendar.to_gregorian(&hijri(99_999_999, 1, 1)).is_err());
+        assert!(calendar.to_gregorian(&hijri(1447, 2, 30)).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use hijri_event_bot::{
+    api::CurrentDateResponse,
+    calendar::{CalendarMethod, HijriCalendar, HijriYmd},
+    convert::{DateConverter, DateQuery},
+    error::AppErrorKind,
+    provider::{HijriDateProvider, ProviderFuture},
+};
+
+use crate::support::i18n;
+
+mod support;
+
+fn gregorian(year: i32, month: u32, day: u32) -> Option<DateQuery> {
+    Some(DateQuery::Gregorian(
+        NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+    ))
+}
+
+fn hijri(year: i32, month: u8, day: u8) -> Option<DateQuery> {
+    Some(DateQuery::Hijri(HijriYmd { year, month, day }))
+}
+
+#[test]
+fn parses_gregorian_dates() {
+    for input in [
+        "1995-07-11",
+        "11.07.1995",
+        "11.07.1995.",
+        "11. 7. 1995. godine",
+        "11/07/1995",
+        "11 jul 1995",
+        "11. jula 1995.",
+        "July 11, 1995",
+    ] {
+        assert_eq!(DateQuery::parse(input), gregorian(1995, 7, 11), "{}", input);
+    }
+}
+
+#[test]
+fn parses_hijri_dates() {
+    for input in [
+        "27 redžeb 1448",
+        "27. redžeba 1448.",
+        "27 Rajab 1448 AH",
+        "1448 rajab 27",
+        "27.07.1448",
+        "1448-07-27",
+        "27.07.1448. h",
+    ] {
+        assert_eq!(DateQuery::parse(input), hijri(1448, 7, 27), "{}", input);
+    }
+}
+
+#[test]
+fn rejects_unparseable_dates() {
+    for input in [
+        "",
+        "danas",
+        "11.07",
+        "31.02.2026",
+        "11.13.1448",
+        "11 jula 1995 h",
+        "11 foo 1995",
+        "1.2.3.4",
+        "1.1.99999999 h",
+        "01.01.200000",
+        "0.1.0",
+    ] {
+        assert_eq!(DateQuery::parse(input), None, "{}", input);
+    }
+}
+
+/// Starts every month a day before the offline calendar, as if the moon was sighted earlier
+struct EarlierMonthStart(HijriCalendar);
+
+impl HijriDateProvider for EarlierMonthStart {
+    fn name(&self) -> &'static str {
+        "earlier"
+    }
+
+    fn get_hijri_date<'a>(&'a self, date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(
+            self.0
+                .calculate_hijri_date(date + chrono::Duration::days(1)),
+        )
+    }
+}
+
+/// Answers the same Hijri date for every day
+struct FixedAnswer(HijriYmd);
+
+impl HijriDateProvider for FixedAnswer {
+    fn name(&self) -> &'static str {
+        "fixed"
+    }
+
+    fn get_hijri_date<'a>(&'a self, _date: NaiveDate) -> ProviderFuture<'a, CurrentDateResponse> {
+        Box::pin(async move { Ok(CurrentDateResponse::from_hijri_ymd(&self.0, &i18n())) })
+    }
+}
+
+#[tokio::test]
+async fn converts_in_both_directions() {
+    let calendar = Arc::new(HijriCalendar::new(CalendarMethod::UmmAlQura, i18n()));
+    let converter = DateConverter::new(calendar.clone(), calendar);
+
+    let (date, response) = converter.convert(hijri(1447, 9, 1).unwrap()).await.unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2026, 2, 18).unwrap());
+    assert_eq!(response.to_string(), "1447-09-01 (Ramazan)");
+
+    let (date, response) = converter
+        .convert(gregorian(2026, 3, 20).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2026, 3, 20).unwrap());
+    assert_eq!(response.to_string(), "1447-10-01 (Ševval)");
+}
+
+#[tokio::test]
+async fn follows_the_provider_month_start() {
+    let calendar = Arc::new(HijriCalendar::new(CalendarMethod::UmmAlQura, i18n()));
+    let provider = Arc::new(EarlierMonthStart(HijriCalendar::new(
+        CalendarMethod::UmmAlQura,
+        i18n(),
+    )));
+    let converter = DateConverter::new(calendar, provider);
+
+    let (date, response) = converter.convert(hijri(1447, 9, 1).unwrap()).await.unwrap();
+
+    assert_eq!(date, NaiveDate::from_ymd_opt(2026, 2, 17).unwrap());
+    assert_eq!(response.to_string(), "1447-09-01 (Ramazan)");
+}
+
+#[tokio::test]
+async fn rejects_an_answer_the_calendar_cannot_place() {
+    let calendar = Arc::new(HijriCalendar::new(CalendarMethod::UmmAlQura, i18n()));
+    assert_eq!(calendar.days_in_month(1447, 8), Some(29));
+    let provider = Arc::new(FixedAnswer(HijriYmd {
+        year: 1447,
+        month: 8,
+        day: 30,
+    }));
+    let converter = DateConverter::new(calendar, provider);
+
+    let result = converter.convert(hijri(1447, 9, 1).unwrap()).await;
+
+    assert!(matches!(result, Err(AppErrorKind::DateConversion)));
+}
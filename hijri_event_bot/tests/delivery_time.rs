@@ -0,0 +1,48 @@
+use bot_core::delivery_time::parse_delivery_time;
+use chrono::{NaiveDate, NaiveTime, TimeZone};
+use hijri_event_bot::{
+    islamic_day::{delivery_of, evening_of},
+    location::Location,
+};
+
+#[test]
+fn parses_delivery_times() {
+    assert_eq!(
+        parse_delivery_time("20:30"),
+        NaiveTime::from_hms_opt(20, 30, 0)
+    );
+    assert_eq!(
+        parse_delivery_time(" 7.05 "),
+        NaiveTime::from_hms_opt(7, 5, 0)
+    );
+    assert_eq!(
+        parse_delivery_time("21h"),
+        NaiveTime::from_hms_opt(21, 0, 0)
+    );
+    assert_eq!(parse_delivery_time("24:00"), None);
+    assert_eq!(parse_delivery_time("20:60"), None);
+    assert_eq!(parse_delivery_time("20:300"), None);
+    assert_eq!(parse_delivery_time("uveče"), None);
+}
+
+#[test]
+fn delivers_at_the_chosen_time_or_in_the_evening() {
+    let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+    let sarajevo = Location::new(43.8563, 18.4131);
+
+    assert_eq!(
+        delivery_of(
+            date,
+            chrono_tz::Europe::Sarajevo,
+            sarajevo,
+            NaiveTime::from_hms_opt(8, 15, 0)
+        ),
+        chrono_tz::Europe::Sarajevo
+            .with_ymd_and_hms(2026, 3, 10, 8, 15, 0)
+            .single()
+    );
+    assert_eq!(
+        delivery_of(date, chrono_tz::Europe::Sarajevo, sarajevo, None),
+        evening_of(date, chrono_tz::Europe::Sarajevo, sarajevo)
+    );
+}
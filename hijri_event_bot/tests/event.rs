@@ -0,0 +1,155 @@
+use chrono::NaiveDate;
+use hijri_event_bot::{
+    calendar::HijriYmd,
+    event::{EventCatalog, LeadTime},
+};
+
+use crate::support::i18n;
+
+mod support;
+
+#[test]
+fn loads_the_bundled_catalog() {
+    let events = EventCatalog::new().unwrap();
+
+    assert_eq!(events.events().first().unwrap().id, "islamic_new_year");
+    assert!(events.find("laylat_al_qadr").unwrap().night);
+    assert_eq!(events.lead_days(), vec![0, 3]);
+    assert_eq!(
+        events
+            .in_month(12)
+            .map(|event| event.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["arafah", "eid_al_adha"]
+    );
+}
+
+#[test]
+fn parses_lead_times() {
+    assert_eq!("evening".parse().ok(), Some(LeadTime::Evening));
+    assert_eq!("0 days".parse().ok(), Some(LeadTime::Evening));
+    assert_eq!("1 day".parse().ok(), Some(LeadTime::DaysBefore(1)));
+    assert_eq!(" 7 Days ".parse().ok(), Some(LeadTime::DaysBefore(7)));
+    assert_eq!("week".parse::<LeadTime>().ok(), None);
+}
+
+#[test]
+fn announces_events_at_their_lead_times() {
+    let i18n = i18n();
+    let events = EventCatalog::from_toml(
+        r#"
+            [[events]]
+            id = "first_ten_days"
+            month = 12
+            day = 1
+            end_day = 10
+            lead_times = ["2 days"]
+            name.ba = "Prvih deset dana zu-l-hidždžeta"
+
+            [[events]]
+            id = "night"
+            month = 9
+            day = 27
+            night = true
+            lead_times = ["evening"]
+            name.ba = "Lejletul-kadr"
+            text.ba = "Tekst"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(events.announced(12, 1, 0).count(), 0);
+    let first_ten_days = events.announced(12, 1, 2).next().unwrap();
+    assert!(first_ten_days.falls_on(12, 10));
+    assert!(!first_ten_days.falls_on(12, 11));
+    assert_eq!(
+        first_ten_days.announcement(2, NaiveDate::from_ymd_opt(2027, 5, 8).unwrap(), &i18n),
+        "Za 2 dana, 08.05.2027., je Prvih deset dana zu-l-hidždžeta."
+    );
+
+    let night = events.announced(9, 27, 0).next().unwrap();
+    assert_eq!(
+        night.announcement(0, NaiveDate::from_ymd_opt(2027, 3, 5).unwrap(), &i18n),
+        "Večeras, nakon akšama, nastupa Lejletul-kadr.\n\nTekst"
+    );
+}
+
+#[test]
+fn rejects_invalid_catalogs() {
+    assert!(
+        EventCatalog::from_toml("[[events]]\nid = \"a\"\nmonth = 13\nday = 1\nname.ba = \"A\"")
+            .is_err()
+    );
+    assert!(
+        EventCatalog::from_toml(
+            "[[events]]\nid = \"a\"\nmonth = 1\nday = 5\nend_day = 3\nname.ba = \"A\""
+        )
+        .is_err()
+    );
+    assert!(
+        EventCatalog::from_toml(
+            "[[events]]\nid = \"a\"\nmonth = 1\nday = 1\nlead_times = [\"soon\"]\nname.ba = \"A\""
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn orders_the_upcoming_events() {
+    let events = EventCatalog::new().unwrap();
+    let today = HijriYmd {
+        year: 1448,
+        month: 9,
+        day: 27,
+    };
+
+    let upcoming: Vec<(&str, i32)> = events
+        .upcoming(&today)
+        .into_iter()
+        .take(4)
+        .map(|(event, start)| (event.id.as_str(), start.year))
+        .collect();
+
+    assert_eq!(
+        upcoming,
+        vec![
+            ("laylat_al_qadr", 1448),
+            ("eid_al_fitr", 1448),
+            ("arafah", 1448),
+            ("eid_al_adha", 1448)
+        ]
+    );
+    assert_eq!(
+        events.find("ramadan").unwrap().next_start(&today),
+        HijriYmd {
+            year: 1449,
+            month: 9,
+            day: 1
+        }
+    );
+}
+
+#[test]
+fn finds_events_by_their_aliases() {
+    let events = EventCatalog::new().unwrap();
+
+    assert_eq!(events.find("Ramazan").unwrap().id, "ramadan");
+    assert_eq!(events.find("kurban_bajram").unwrap().id, "eid_al_adha");
+    assert!(events.find("christmas").is_none());
+}
+
+#[test]
+fn counts_down_to_the_events() {
+    let i18n = i18n();
+    let events = EventCatalog::new().unwrap();
+    let ramadan = events.find("ramadan").unwrap();
+    let laylat_al_qadr = events.find("laylat_al_qadr").unwrap();
+
+    assert_eq!(ramadan.countdown(-3, &i18n), "u toku");
+    assert_eq!(ramadan.countdown(0, &i18n), "danas");
+    assert_eq!(ramadan.countdown(1, &i18n), "sutra");
+    assert_eq!(ramadan.countdown(21, &i18n), "za 21 dan");
+    assert_eq!(ramadan.countdown(111, &i18n), "za 111 dana");
+    assert_eq!(laylat_al_qadr.countdown(1, &i18n), "večeras");
+    assert_eq!(laylat_al_qadr.countdown(5, &i18n), "za 4 dana");
+}
@@ -0,0 +1,77 @@
+use hijri_event_bot::fasting::{FastingReason, FastingRuling, FastingStatus, white_days};
+
+use crate::support::date;
+
+mod support;
+
+fn ruling(status: FastingStatus, reason: FastingReason) -> FastingRuling {
+    FastingRuling {
+        status,
+        reason: Some(reason),
+    }
+}
+
+#[test]
+fn forbids_fasting_on_eid_and_tashreeq() {
+    // 19.10.2026. is a Monday, which doesn't make the forbidden days recommended
+    let monday = date(2026, 10, 19);
+
+    assert_eq!(
+        FastingRuling::of(monday, 10, 1),
+        ruling(FastingStatus::Forbidden, FastingReason::EidAlFitr)
+    );
+    assert_eq!(
+        FastingRuling::of(monday, 12, 10),
+        ruling(FastingStatus::Forbidden, FastingReason::EidAlAdha)
+    );
+    assert_eq!(
+        FastingRuling::of(monday, 12, 13),
+        ruling(FastingStatus::Forbidden, FastingReason::Tashreeq)
+    );
+    assert_eq!(
+        FastingRuling::of(monday, 9, 13),
+        ruling(FastingStatus::Obligatory, FastingReason::Ramadan)
+    );
+}
+
+#[test]
+fn classifies_the_other_days() {
+    let tuesday = date(2026, 10, 20);
+
+    assert_eq!(
+        FastingRuling::of(tuesday, 12, 9),
+        ruling(FastingStatus::Recommended, FastingReason::Arafah)
+    );
+    assert_eq!(
+        FastingRuling::of(tuesday, 5, 14),
+        ruling(FastingStatus::Recommended, FastingReason::WhiteDays)
+    );
+    assert_eq!(
+        FastingRuling::of(date(2026, 10, 22), 5, 11),
+        ruling(FastingStatus::Recommended, FastingReason::MondayThursday)
+    );
+    assert_eq!(
+        FastingRuling::of(date(2026, 10, 23), 5, 12),
+        ruling(FastingStatus::Disliked, FastingReason::Friday)
+    );
+    assert_eq!(
+        FastingRuling::of(tuesday, 8, 30),
+        ruling(FastingStatus::Disliked, FastingReason::DayOfDoubt)
+    );
+    assert_eq!(
+        FastingRuling::of(tuesday, 5, 8),
+        FastingRuling {
+            status: FastingStatus::Permissible,
+            reason: None
+        }
+    );
+}
+
+#[test]
+fn skips_the_white_days_on_which_fasting_isnt_recommended() {
+    let thirteenth = date(2026, 10, 20);
+
+    assert_eq!(white_days(5, thirteenth), vec![13, 14, 15]);
+    assert_eq!(white_days(12, thirteenth), vec![14, 15]);
+    assert!(white_days(9, thirteenth).is_empty());
+}
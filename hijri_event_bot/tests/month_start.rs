@@ -0,0 +1,73 @@
+use hijri_event_bot::{
+    calendar::{CalendarMethod, HijriCalendar, HijriYmd},
+    provider::month_start::{MonthStart, shifted_hijri_date},
+};
+
+use crate::support::{date, i18n};
+
+mod support;
+
+fn hijri(year: i32, month: u8, day: u8) -> Option<HijriYmd> {
+    Some(HijriYmd { year, month, day })
+}
+
+#[test]
+fn keeps_a_late_start_until_the_next_announcement() {
+    let calendar = HijriCalendar::new(CalendarMethod::UmmAlQura, i18n());
+    // The calendar starts Ramadan 1447 on 18 February 2026
+    let ramadan = MonthStart {
+        year: 1447,
+        month: 9,
+        start: date(2026, 2, 19),
+    };
+
+    let on = |day| shifted_hijri_date(&calendar, day, Some(&ramadan), None);
+
+    assert_eq!(on(date(2026, 2, 19)), hijri(1447, 9, 1));
+    assert_eq!(on(date(2026, 3, 20)), hijri(1447, 9, 30));
+    assert_eq!(on(date(2026, 3, 21)), hijri(1447, 10, 1));
+    assert_eq!(on(date(2026, 5, 1)), hijri(1447, 11, 13));
+}
+
+#[test]
+fn keeps_the_eve_of_a_late_start_in_the_month_before() {
+    let calendar = HijriCalendar::new(CalendarMethod::UmmAlQura, i18n());
+    let ramadan = MonthStart {
+        year: 1447,
+        month: 9,
+        start: date(2026, 2, 19),
+    };
+
+    assert_eq!(
+        shifted_hijri_date(&calendar, date(2026, 2, 18), None, Some(&ramadan)),
+        hijri(1447, 8, 30)
+    );
+    assert_eq!(
+        shifted_hijri_date(&calendar, date(2026, 2, 17), None, Some(&ramadan)),
+        None
+    );
+}
+
+#[test]
+fn follows_an_early_start_after_a_late_one() {
+    let calendar = HijriCalendar::new(CalendarMethod::UmmAlQura, i18n());
+    let ramadan = MonthStart {
+        year: 1447,
+        month: 9,
+        start: date(2026, 2, 19),
+    };
+    let shawwal = MonthStart {
+        year: 1447,
+        month: 10,
+        start: date(2026, 3, 20),
+    };
+
+    assert_eq!(
+        shifted_hijri_date(&calendar, date(2026, 3, 19), Some(&ramadan), Some(&shawwal)),
+        hijri(1447, 9, 29)
+    );
+    assert_eq!(
+        shifted_hijri_date(&calendar, date(2026, 3, 20), Some(&shawwal), None),
+        hijri(1447, 10, 1)
+    );
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use hijri_event_bot::{
+    calendar::{CalendarMethod, HijriCalendar},
+    convert::DateConverter,
+    event::EventCatalog,
+    month_view::MonthView,
+};
+
+use crate::support::{date, i18n};
+
+mod support;
+
+#[tokio::test]
+async fn renders_the_month_grid() {
+    let i18n = i18n();
+    let calendar = Arc::new(HijriCalendar::new(CalendarMethod::UmmAlQura, i18n.clone()));
+    let converter = DateConverter::new(calendar.clone(), calendar);
+
+    let view = MonthView::load(&converter, &EventCatalog::new().unwrap(), 1447, 12)
+        .await
+        .unwrap();
+    let message = view.render(date(2026, 5, 20), &i18n);
+
+    assert!(message.starts_with("<b>Zu-l-hidždže 1447. / ذوالحجة\n18.05.2026. – 15.06.2026.</b>"));
+    assert!(message.contains(
+        "\n  1   2 [ 3]  4   5   6   7\n 18  19  20  21  22  23  24\n\n  8   9! 10! 11  12  13 (14)\n"
+    ));
+    assert!(message.contains("\n 29\n 15</pre>"));
+    assert!(message.ends_with("10. Zu-l-hidždže (27.05.2026.) – Kurban-bajram"));
+}
+
+#[tokio::test]
+async fn marks_the_white_days_which_may_be_fasted() {
+    let i18n = i18n();
+    let calendar = Arc::new(HijriCalendar::new(CalendarMethod::UmmAlQura, i18n.clone()));
+    let converter = DateConverter::new(calendar.clone(), calendar);
+    let events = EventCatalog::new().unwrap();
+
+    // The 13th of Dhul-Hijjah is a day of Tashreeq, on which fasting is forbidden
+    let dhul_hijjah = MonthView::load(&converter, &events, 1447, 12)
+        .await
+        .unwrap()
+        .render(date(2026, 1, 1), &i18n);
+    assert!(!dhul_hijjah.contains("(13)"));
+    assert!(dhul_hijjah.contains("(14)"));
+    assert!(dhul_hijjah.contains("(15)"));
+
+    let ramadan = MonthView::load(&converter, &events, 1447, 9)
+        .await
+        .unwrap()
+        .render(date(2026, 1, 1), &i18n);
+    assert!(!ramadan.contains("(13)") && !ramadan.contains("(14)") && !ramadan.contains("(15)"));
+
+    let shaban = MonthView::load(&converter, &events, 1447, 8)
+        .await
+        .unwrap()
+        .render(date(2026, 1, 1), &i18n);
+    assert!(shaban.contains("(13)"));
+}
+
+#[test]
+fn pages_across_the_years() {
+    assert_eq!(
+        MonthView::parse_callback_data("calendar:1448:1"),
+        Some((1448, 1))
+    );
+    assert_eq!(MonthView::parse_callback_data("calendar:1448:13"), None);
+    assert_eq!(MonthView::parse_callback_data("settings:1448:1"), None);
+}
+
+#[tokio::test]
+async fn links_the_neighbouring_months() {
+    let i18n = i18n();
+    let calendar = Arc::new(HijriCalendar::new(CalendarMethod::UmmAlQura, i18n.clone()));
+    let converter = DateConverter::new(calendar.clone(), calendar);
+
+    let view = MonthView::load(&converter, &EventCatalog::new().unwrap(), 1447, 12)
+        .await
+        .unwrap();
+
+    assert_eq!(view.previous(), (1447, 11));
+    assert_eq!(view.next(), (1448, 1));
+}
@@ -0,0 +1,32 @@
+use bot_core::bot_core::BotCore;
+use hijri_event_bot::notification::NotificationCategory;
+use teloxide::{ApiError, RequestError};
+
+#[test]
+fn parses_the_categories_of_the_callback_data() {
+    for category in NotificationCategory::ALL {
+        assert_eq!(category.as_str().parse().ok(), Some(category));
+    }
+
+    assert_eq!(
+        "white_days".parse().ok(),
+        Some(NotificationCategory::WhiteDays)
+    );
+    assert_eq!("hadith".parse::<NotificationCategory>().ok(), None);
+}
+
+#[test]
+fn stops_sending_to_unreachable_chats() {
+    assert!(BotCore::is_chat_unreachable(&RequestError::Api(
+        ApiError::BotBlocked
+    )));
+    assert!(BotCore::is_chat_unreachable(&RequestError::Api(
+        ApiError::UserDeactivated
+    )));
+    assert!(BotCore::is_chat_unreachable(&RequestError::Api(
+        ApiError::ChatNotFound
+    )));
+    assert!(!BotCore::is_chat_unreachable(&RequestError::Api(
+        ApiError::MessageIsTooLong
+    )));
+}
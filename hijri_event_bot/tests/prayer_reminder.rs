@@ -0,0 +1,81 @@
+use chrono::{DateTime, TimeZone, Utc};
+use hijri_event_bot::{
+    prayer_reminder::{PrayerReminder, due_reminders, next_reminder_time},
+    prayer_times::{Prayer, PrayerTimesConfig},
+};
+
+use crate::support::sarajevo;
+
+mod support;
+
+fn local(hour: u32, minute: u32) -> DateTime<Utc> {
+    chrono_tz::Europe::Sarajevo
+        .with_ymd_and_hms(2026, 6, 21, hour, minute, 0)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+fn reminders() -> Vec<PrayerReminder> {
+    vec![
+        PrayerReminder::new(Prayer::Fajr, 0).unwrap(),
+        PrayerReminder::new(Prayer::Maghrib, 10).unwrap(),
+    ]
+}
+
+fn next_after(after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    next_reminder_time(
+        &reminders(),
+        sarajevo(),
+        chrono_tz::Europe::Sarajevo,
+        &PrayerTimesConfig::default(),
+        after,
+    )
+}
+
+#[test]
+fn finds_the_next_reminder() {
+    // Maghrib is at 20:32 and Fajr of the next day at 02:27
+    assert_eq!(next_after(local(12, 0)), Some(local(20, 22)));
+    assert_eq!(
+        next_after(local(20, 22)),
+        Some(local(2, 27) + chrono::Duration::days(1))
+    );
+}
+
+#[test]
+fn sends_the_reminders_due_at_the_moment() {
+    let due = due_reminders(
+        &reminders(),
+        sarajevo(),
+        chrono_tz::Europe::Sarajevo,
+        &PrayerTimesConfig::default(),
+        local(20, 22),
+    );
+
+    assert_eq!(
+        due,
+        vec![(
+            PrayerReminder::new(Prayer::Maghrib, 10).unwrap(),
+            local(20, 32)
+        )]
+    );
+    assert!(
+        due_reminders(
+            &reminders(),
+            sarajevo(),
+            chrono_tz::Europe::Sarajevo,
+            &PrayerTimesConfig::default(),
+            local(20, 30),
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn limits_the_minutes_before() {
+    assert!(PrayerReminder::new(Prayer::Isha, 180).is_some());
+    assert!(PrayerReminder::new(Prayer::Isha, 181).is_none());
+    assert!(PrayerReminder::new(Prayer::Isha, -5).is_none());
+    assert_eq!("akšam".parse().ok(), Some(Prayer::Maghrib));
+    assert_eq!("zora".parse().ok(), Some(Prayer::Fajr));
+}
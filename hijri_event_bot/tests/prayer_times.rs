@@ -0,0 +1,106 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use hijri_event_bot::{
+    location::Location,
+    prayer_times::{AsrMethod, CalculationMethod, Prayer, PrayerTimes, PrayerTimesConfig},
+};
+
+use crate::support::sarajevo;
+
+mod support;
+
+fn summer_solstice() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 6, 21).unwrap()
+}
+
+fn local_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&chrono_tz::Europe::Sarajevo)
+        .format("%H:%M")
+        .to_string()
+}
+
+fn calculate(method: CalculationMethod, asr: AsrMethod) -> PrayerTimes {
+    PrayerTimes::calculate(
+        summer_solstice(),
+        sarajevo(),
+        &PrayerTimesConfig { method, asr },
+    )
+    .unwrap()
+}
+
+#[test]
+fn calculates_the_prayer_times_in_sarajevo() {
+    let prayer_times =
+        PrayerTimes::calculate(summer_solstice(), sarajevo(), &PrayerTimesConfig::default())
+            .unwrap();
+    let times: Vec<String> = Prayer::ALL
+        .iter()
+        .map(|prayer| local_time(prayer_times.time(*prayer)))
+        .collect();
+
+    assert_eq!(
+        times,
+        ["02:27", "05:04", "12:48", "16:55", "20:32", "22:45"]
+    );
+}
+
+#[test]
+fn follows_the_calculation_method() {
+    let mwl = calculate(CalculationMethod::MuslimWorldLeague, AsrMethod::Standard);
+    let isna = calculate(CalculationMethod::Isna, AsrMethod::Standard);
+    let umm_al_qura = calculate(CalculationMethod::UmmAlQura, AsrMethod::Standard);
+    let diyanet = calculate(CalculationMethod::Diyanet, AsrMethod::Standard);
+
+    // A smaller angle means a later Fajr and an earlier Isha
+    assert!(isna.fajr > mwl.fajr);
+    assert!(isna.isha < mwl.isha);
+    assert_eq!(
+        umm_al_qura.isha - umm_al_qura.maghrib,
+        chrono::Duration::minutes(90)
+    );
+    assert_eq!(diyanet.sunrise - mwl.sunrise, chrono::Duration::minutes(-7));
+    assert_eq!(diyanet.maghrib - mwl.maghrib, chrono::Duration::minutes(7));
+}
+
+#[test]
+fn calculates_the_hanafi_asr_later() {
+    let standard = calculate(CalculationMethod::IzBih, AsrMethod::Standard);
+    let hanafi = calculate(CalculationMethod::IzBih, AsrMethod::Hanafi);
+
+    assert_eq!(local_time(hanafi.asr), "18:09");
+    assert_eq!(hanafi.dhuhr, standard.dhuhr);
+}
+
+#[test]
+fn keeps_the_prayers_in_order_at_high_latitudes() {
+    let stockholm = Location::new(59.3293, 18.0686).unwrap();
+    let prayer_times =
+        PrayerTimes::calculate(summer_solstice(), stockholm, &PrayerTimesConfig::default())
+            .unwrap();
+    let times: Vec<DateTime<Utc>> = Prayer::ALL
+        .iter()
+        .map(|prayer| prayer_times.time(*prayer))
+        .collect();
+
+    assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let tromso = Location::new(69.6492, 18.9553).unwrap();
+    assert!(
+        PrayerTimes::calculate(summer_solstice(), tromso, &PrayerTimesConfig::default()).is_none()
+    );
+}
+
+#[test]
+fn parses_the_methods() {
+    assert_eq!(
+        "MWL".parse().ok(),
+        Some(CalculationMethod::MuslimWorldLeague)
+    );
+    assert_eq!(
+        "umm-al-qura".parse().ok(),
+        Some(CalculationMethod::UmmAlQura)
+    );
+    assert_eq!("iz_bih".parse().ok(), Some(CalculationMethod::IzBih));
+    assert!("karachi".parse::<CalculationMethod>().is_err());
+    assert_eq!("hanefijska".parse().ok(), Some(AsrMethod::Hanafi));
+    assert_eq!("standardna".parse().ok(), Some(AsrMethod::Standard));
+}
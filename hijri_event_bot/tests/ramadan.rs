@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use hijri_event_bot::{
+    prayer_times::{PrayerTimes, PrayerTimesConfig},
+    ramadan::{FastReminder, evening_messages},
+};
+
+use crate::support::{i18n, sarajevo};
+
+mod support;
+
+fn sarajevo_prayer_times() -> PrayerTimes {
+    PrayerTimes::calculate(
+        NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(),
+        sarajevo(),
+        &PrayerTimesConfig::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn reminds_of_suhoor_and_iftar() {
+    let prayer_times = sarajevo_prayer_times();
+    let window = chrono::Duration::minutes(1);
+    let suhoor = prayer_times.fajr - chrono::Duration::minutes(30);
+
+    assert_eq!(
+        FastReminder::due(&prayer_times, suhoor, window),
+        vec![FastReminder::Suhoor]
+    );
+    assert_eq!(
+        FastReminder::due(
+            &prayer_times,
+            prayer_times.maghrib + chrono::Duration::seconds(20),
+            window
+        ),
+        vec![FastReminder::Iftar]
+    );
+    assert!(FastReminder::due(&prayer_times, prayer_times.maghrib + window, window).is_empty());
+    assert!(FastReminder::due(&prayer_times, prayer_times.fajr, window).is_empty());
+}
+
+#[test]
+fn sends_the_evening_messages_of_the_day() {
+    let i18n = i18n();
+    let prayer_times = sarajevo_prayer_times();
+    let timezone = chrono_tz::Europe::Sarajevo;
+
+    let messages = evening_messages(5, Some(&prayer_times), timezone, &i18n);
+    assert_eq!(
+        messages,
+        vec![
+            "Večeras počinje 5. noć ramazana, a sutra je 5. dan posta.\n\nSehur je do 01:57 (zora je u 02:27), a iftar u 20:32."
+        ]
+    );
+
+    assert_eq!(evening_messages(20, None, timezone, &i18n).len(), 1);
+    assert_eq!(evening_messages(21, None, timezone, &i18n).len(), 2);
+    assert_eq!(evening_messages(28, None, timezone, &i18n).len(), 2);
+}
@@ -0,0 +1,70 @@
+use chrono::{NaiveDate, NaiveTime, TimeZone};
+use hijri_event_bot::{islamic_day::evening_of, location::Location, solar::sunset};
+
+use crate::support::sarajevo;
+
+mod support;
+
+fn local_sunset(date: NaiveDate) -> NaiveTime {
+    sunset(date, sarajevo())
+        .unwrap()
+        .with_timezone(&chrono_tz::Europe::Sarajevo)
+        .time()
+}
+
+fn assert_close(actual: NaiveTime, expected: NaiveTime) {
+    let difference = (actual - expected).num_seconds().abs();
+
+    assert!(
+        difference <= 120,
+        "expected about {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn computes_the_sunset_in_sarajevo() {
+    assert_close(
+        local_sunset(NaiveDate::from_ymd_opt(2026, 6, 21).unwrap()),
+        NaiveTime::from_hms_opt(20, 32, 0).unwrap(),
+    );
+    assert_close(
+        local_sunset(NaiveDate::from_ymd_opt(2026, 12, 21).unwrap()),
+        NaiveTime::from_hms_opt(16, 11, 0).unwrap(),
+    );
+}
+
+#[test]
+fn has_no_sunset_during_the_polar_day_and_night() {
+    let tromso = Location::new(69.6492, 18.9553).unwrap();
+
+    assert!(sunset(NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(), tromso).is_none());
+    assert!(sunset(NaiveDate::from_ymd_opt(2026, 12, 21).unwrap(), tromso).is_none());
+    assert!(sunset(NaiveDate::from_ymd_opt(2026, 3, 21).unwrap(), tromso).is_some());
+}
+
+#[test]
+fn falls_back_to_the_default_evening_without_a_location() {
+    let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+    assert_eq!(
+        evening_of(date, chrono_tz::America::Chicago, None),
+        chrono_tz::America::Chicago
+            .with_ymd_and_hms(2026, 3, 10, 18, 0, 0)
+            .single()
+    );
+    assert_eq!(
+        evening_of(date, chrono_tz::Europe::Sarajevo, Some(sarajevo())),
+        sunset(date, sarajevo()).map(|sunset| sunset.with_timezone(&chrono_tz::Europe::Sarajevo))
+    );
+}
+
+#[test]
+fn parses_coordinates() {
+    assert_eq!(Location::parse("43.8563 18.4131"), Some(sarajevo()));
+    assert_eq!(Location::parse("43.8563, 18.4131"), Some(sarajevo()));
+    assert_eq!(Location::parse("93 18"), None);
+    assert_eq!(Location::parse("43.8563"), None);
+    assert_eq!(Location::parse("43 18 5"), None);
+}
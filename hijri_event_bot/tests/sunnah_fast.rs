@@ -0,0 +1,49 @@
+use hijri_event_bot::sunnah_fast::SunnahFast;
+
+use crate::support::date;
+
+mod support;
+
+#[test]
+fn reminds_of_mondays_and_thursdays_outside_ramadan_and_eid() {
+    let fast = SunnahFast::MondayThursday;
+
+    assert!(fast.reminder(date(2026, 10, 19), 5, 7).is_some());
+    assert!(fast.reminder(date(2026, 10, 22), 5, 10).is_some());
+    assert!(fast.reminder(date(2026, 10, 20), 5, 8).is_none());
+    assert!(fast.reminder(date(2026, 10, 19), 9, 7).is_none());
+    assert!(fast.reminder(date(2026, 10, 19), 10, 1).is_none());
+    assert!(fast.reminder(date(2026, 10, 19), 12, 13).is_none());
+}
+
+#[test]
+fn reminds_of_the_fasts_of_the_hijri_year() {
+    let today = date(2026, 10, 20);
+
+    assert!(SunnahFast::Ashura.reminder(today, 1, 9).is_some());
+    assert!(SunnahFast::Ashura.reminder(today, 1, 10).is_some());
+    assert!(SunnahFast::Ashura.reminder(today, 1, 11).is_none());
+    assert!(SunnahFast::Arafah.reminder(today, 12, 9).is_some());
+    assert!(SunnahFast::Arafah.reminder(today, 12, 10).is_none());
+    assert!(SunnahFast::DhulHijjah.reminder(today, 12, 1).is_some());
+    assert!(SunnahFast::DhulHijjah.reminder(today, 12, 10).is_none());
+    assert!(SunnahFast::Shawwal.reminder(today, 10, 1).is_none());
+    assert!(SunnahFast::Shawwal.reminder(today, 10, 2).is_some());
+    assert!(SunnahFast::Shawwal.reminder(today, 11, 2).is_none());
+}
+
+#[test]
+fn parses_the_fasts() {
+    assert_eq!("Ponedjeljak".parse().ok(), Some(SunnahFast::MondayThursday));
+    assert_eq!("asura".parse().ok(), Some(SunnahFast::Ashura));
+    assert_eq!("sevval".parse().ok(), Some(SunnahFast::Shawwal));
+    assert_eq!("ramazan".parse::<SunnahFast>().ok(), None);
+
+    for fast in SunnahFast::ALL {
+        assert_eq!(fast.as_str().parse().ok(), Some(fast));
+        assert_eq!(
+            SunnahFast::from_extension_type(fast.extension_type() as i32),
+            Some(fast)
+        );
+    }
+}
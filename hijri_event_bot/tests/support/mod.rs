@@ -0,0 +1,211 @@
+//! Helpers shared by the integration tests, each of which uses only some of them
+
+#![allow(dead_code)]
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use chrono::NaiveDate;
+use hijri_event_bot::{i18n::instance::I18n, location::Location};
+
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+pub fn i18n() -> Arc<I18n> {
+    Arc::new(I18n::new().expect("Failed to initialize i18n"))
+}
+
+pub fn sarajevo() -> Location {
+    Location::new(43.8563, 18.4131).unwrap()
+}
+
+/// Reads a response recorded from the Aladhan API from `tests/fixtures`
+pub fn fixture(name: &str) -> String {
+    let path = format!(
+        "{}/tests/fixtures/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Missing fixture {}: {}", path, e))
+}
+
+#[derive(Clone)]
+pub struct MockResponse {
+    status: u16,
+    body: String,
+    delay: Option<Duration>,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            body: body.to_string(),
+            delay: None,
+        }
+    }
+
+    pub fn fixture(name: &str) -> Self {
+        Self::new(200, &fixture(name))
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self::new(status, "")
+    }
+
+    /// Waits before answering, to simulate a slow API
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+struct MockRoute {
+    path_prefix: String,
+    responses: VecDeque<MockResponse>,
+}
+
+#[derive(Default)]
+struct MockState {
+    routes: Vec<MockRoute>,
+    requests: Vec<String>,
+}
+
+impl MockState {
+    /// Takes the next queued response of the first matching route. The last response of a
+    /// route is kept, so it answers all the following requests.
+    fn next_response(&mut self, path: &str) -> MockResponse {
+        let route = self
+            .routes
+            .iter_mut()
+            .find(|route| path.starts_with(&route.path_prefix));
+
+        match route {
+            Some(route) if route.responses.len() > 1 => route.responses.pop_front().unwrap(),
+            Some(route) => route.responses[0].clone(),
+            None => MockResponse::new(
+                404,
+                r#"{"code":404,"status":"NOT_FOUND","data":"Not found"}"#,
+            ),
+        }
+    }
+}
+
+/// In-process stand-in for the Aladhan API, answering on a random local port with the
+/// responses registered for the request paths
+pub struct MockAladhanServer {
+    base_url: String,
+    state: Arc<Mutex<MockState>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockAladhanServer {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind the mock server");
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let handle = tokio::spawn({
+            let state = state.clone();
+
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(handle_connection(stream, state.clone()));
+                }
+            }
+        });
+
+        Self {
+            base_url,
+            state,
+            handle,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Answers the requests whose path (without the `/v1` prefix) starts with `path_prefix`.
+    /// Calling it again for the same prefix queues the responses in order.
+    pub fn respond(&self, path_prefix: &str, response: MockResponse) -> &Self {
+        let mut state = self.state.lock().unwrap();
+
+        match state
+            .routes
+            .iter_mut()
+            .find(|route| route.path_prefix == path_prefix)
+        {
+            Some(route) => route.responses.push_back(response),
+            None => state.routes.push(MockRoute {
+                path_prefix: path_prefix.to_string(),
+                responses: VecDeque::from([response]),
+            }),
+        }
+
+        self
+    }
+
+    /// Paths of all the received requests, without the `/v1` prefix
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockAladhanServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(read) => request.extend_from_slice(&buffer[..read]),
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let path = request
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .trim_start_matches("/v1")
+        .to_string();
+
+    let response = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(path.clone());
+        state.next_response(&path)
+    };
+
+    if let Some(delay) = response.delay {
+        tokio::time::sleep(delay).await;
+    }
+
+    let raw_response = format!(
+        "HTTP/1.1 {} MOCK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    );
+
+    let _ = stream.write_all(raw_response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
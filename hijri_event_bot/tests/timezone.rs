@@ -0,0 +1,31 @@
+use hijri_event_bot::timezone::search_timezones;
+
+#[test]
+fn finds_timezones_by_name_and_city() {
+    assert_eq!(
+        search_timezones("America/Chicago"),
+        vec![chrono_tz::America::Chicago]
+    );
+    assert_eq!(
+        search_timezones("chicago"),
+        vec![chrono_tz::America::Chicago]
+    );
+    assert_eq!(
+        search_timezones(" new york "),
+        vec![chrono_tz::America::New_York]
+    );
+    assert_eq!(
+        search_timezones("sarajevo"),
+        vec![chrono_tz::Europe::Sarajevo]
+    );
+}
+
+#[test]
+fn lists_partial_matches_with_the_region_names_first() {
+    let matches = search_timezones("syd");
+
+    assert_eq!(matches.first(), Some(&chrono_tz::Australia::Sydney));
+    assert!(search_timezones("america").len() > 8);
+    assert!(search_timezones("atlantida").is_empty());
+    assert!(search_timezones("  ").is_empty());
+}
@@ -0,0 +1,132 @@
+use bot_core::db::migrator::{MigrationProject, Migrator};
+use chrono::{NaiveTime, Utc};
+use hijri_event_bot::scheduler::delete_user_data;
+use sqlx::{Pool, Postgres, postgres::PgPoolOptions, types::Uuid};
+
+use crate::support::date;
+
+mod support;
+
+/// The database of the bot, when `DATABASE_URL` points to one
+async fn pool() -> Option<Pool<Postgres>> {
+    let database_url = std::env::var("DATABASE_URL").ok()?;
+    Migrator::run(&database_url, MigrationProject::HijriEventBot)
+        .await
+        .expect("Failed to run the migrations");
+
+    Some(
+        PgPoolOptions::new()
+            .connect(&database_url)
+            .await
+            .expect("Failed to connect to the database"),
+    )
+}
+
+async fn count(pool: &Pool<Postgres>, query: &str, id: Uuid) -> i64 {
+    sqlx::query_scalar(query)
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn deletes_the_jobs_and_the_settings_of_the_user() {
+    let Some(pool) = pool().await else {
+        eprintln!("DATABASE_URL is not set, skipping");
+        return;
+    };
+
+    let chat_id = Utc::now().timestamp_micros();
+    let user_id = Uuid::new_v4();
+    let job_id = Uuid::new_v4();
+    let notification_id = Uuid::new_v4();
+
+    sqlx::query("INSERT INTO users (id, chat_id, delivery_time) VALUES ($1, $2, $3)")
+        .bind(user_id)
+        .bind(chat_id)
+        .bind(NaiveTime::from_hms_opt(7, 0, 0).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO jobs (id, job_type) VALUES ($1, 0)")
+        .bind(job_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO job_extensions (job_id, type) VALUES ($1, 2)")
+        .bind(job_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO users_jobs (id, job_id, user_id, extension_type) VALUES ($1, $2, $3, 2)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(job_id)
+    .bind(user_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO notifications (id, job_id) VALUES ($1, $2)")
+        .bind(notification_id)
+        .bind(job_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO notification_states (id, state) VALUES ($1, 0)")
+        .bind(notification_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO prayer_reminders (user_id, prayer) VALUES ($1, 'fajr')")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO notification_preferences (user_id, category, enabled) VALUES ($1, 'events', FALSE)")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO shawwal_fasts (user_id, year, days) VALUES ($1, 1447, 2)")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO deliveries (user_id, kind, logical_date) VALUES ($1, 'white_days', $2)",
+    )
+    .bind(user_id)
+    .bind(date(2026, 10, 17))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(
+        delete_user_data(&pool, chat_id).await.unwrap(),
+        vec![job_id]
+    );
+
+    for query in [
+        "SELECT COUNT(*) FROM jobs WHERE id = $1",
+        "SELECT COUNT(*) FROM job_extensions WHERE job_id = $1",
+        "SELECT COUNT(*) FROM users_jobs WHERE job_id = $1",
+        "SELECT COUNT(*) FROM notifications WHERE job_id = $1",
+    ] {
+        assert_eq!(count(&pool, query, job_id).await, 0, "{}", query);
+    }
+    for query in [
+        "SELECT COUNT(*) FROM prayer_reminders WHERE user_id = $1",
+        "SELECT COUNT(*) FROM notification_preferences WHERE user_id = $1",
+        "SELECT COUNT(*) FROM shawwal_fasts WHERE user_id = $1",
+        "SELECT COUNT(*) FROM deliveries WHERE user_id = $1",
+        "SELECT COUNT(*) FROM users WHERE id = $1 AND (active OR delivery_time IS NOT NULL)",
+    ] {
+        assert_eq!(count(&pool, query, user_id).await, 0, "{}", query);
+    }
+    assert_eq!(
+        count(&pool, "SELECT COUNT(*) FROM users WHERE id = $1", user_id).await,
+        1
+    );
+}